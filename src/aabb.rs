@@ -0,0 +1,278 @@
+use crate::range::Range;
+use crate::vecn::vecn;
+use crate::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+///Convenience function to create an AABB.
+#[inline(always)]
+pub const fn aabb<T, const D: usize>(ranges: [Range<T>; D]) -> AABB<T, D> {
+    AABB { ranges }
+}
+
+///An axis aligned bounding box of D dimensions. Stored as an array of Ranges.
+///Axis are referred to by their index, so x is 0, y is 1, z is 2, and so on.
+///For D=2 this converts losslessly to and from `Rect`, and for D=3 to and from `Box3`.
+#[derive(Hash, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+#[must_use]
+pub struct AABB<T, const D: usize> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
+    pub ranges: [Range<T>; D],
+}
+
+impl<T: Default, const D: usize> Default for AABB<T, D> {
+    #[inline(always)]
+    fn default() -> Self {
+        aabb(core::array::from_fn(|_| Range::default()))
+    }
+}
+
+impl<T, const D: usize> AABB<T, D> {
+    ///Get the range of one axis.
+    #[inline(always)]
+    pub fn get_range(&self, axis: usize) -> &Range<T> {
+        &self.ranges[axis]
+    }
+
+    ///Get the mutable range of one axis.
+    #[inline(always)]
+    pub fn get_range_mut(&mut self, axis: usize) -> &mut Range<T> {
+        &mut self.ranges[axis]
+    }
+
+    #[inline(always)]
+    pub fn inner_into<A>(self) -> AABB<A, D>
+    where
+        T: Into<A>,
+    {
+        aabb(self.ranges.map(|r| r.inner_into()))
+    }
+}
+
+impl<T: Copy, const D: usize> AABB<T, D> {
+    #[inline(always)]
+    pub fn inner_as<B: 'static + Copy>(&self) -> AABB<B, D>
+    where
+        T: num_traits::AsPrimitive<B>,
+    {
+        aabb(self.ranges.map(|r| r.inner_as()))
+    }
+
+    ///Returns the corner with the smallest value on every axis.
+    #[inline(always)]
+    pub fn min_corner(&self) -> VecN<T, D> {
+        vecn(self.ranges.map(|r| r.start))
+    }
+
+    ///Returns the corner with the largest value on every axis.
+    #[inline(always)]
+    pub fn max_corner(&self) -> VecN<T, D> {
+        vecn(self.ranges.map(|r| r.end))
+    }
+}
+
+impl<T: Copy + core::ops::Sub<Output = T> + core::ops::Add<Output = T>, const D: usize> AABB<T, D> {
+    ///Create a bounding box from a point and radius.
+    #[inline(always)]
+    pub fn from_point(point: VecN<T, D>, radius: VecN<T, D>) -> AABB<T, D> {
+        aabb(core::array::from_fn(|i| {
+            Range::from_point(point.coords[i], radius.coords[i])
+        }))
+    }
+
+    ///Grow a bounding box of a radius.
+    #[inline(always)]
+    pub fn grow(self, radius: T) -> Self {
+        aabb(self.ranges.map(|r| r.grow(radius)))
+    }
+}
+
+impl<T: num_traits::Num + Copy, const D: usize> AABB<T, D> {
+    #[inline(always)]
+    pub fn derive_center(&self) -> VecN<T, D> {
+        let two = T::one() + T::one();
+        vecn(self.ranges.map(|r| r.start + (r.end - r.start) / two))
+    }
+}
+
+impl<
+        T: Copy
+            + PartialOrd
+            + core::ops::Sub<Output = T>
+            + core::ops::Mul<Output = T>
+            + core::ops::Add<Output = T>
+            + num_traits::Zero,
+        const D: usize,
+    > AABB<T, D>
+{
+    ///If the point is outside the bounding box, returns the squared distance from the closest point on the box.
+    ///If the point is inside the bounding box, it will return None.
+    #[inline(always)]
+    pub fn distance_squared_to_point(&self, point: VecN<T, D>) -> Option<T> {
+        let mut inside = true;
+        let mut dis = T::zero();
+        for (r, &p) in self.ranges.iter().zip(point.coords.iter()) {
+            let c = num_traits::clamp(p, r.start, r.end);
+            if !(c > r.start && c < r.end) {
+                inside = false;
+            }
+            dis = dis + (c - p) * (c - p);
+        }
+
+        if inside {
+            None
+        } else {
+            Some(dis)
+        }
+    }
+}
+
+impl<T: PartialOrd + Copy, const D: usize> AABB<T, D> {
+    ///Returns true if the point is contained in the the ranges of every axis.
+    #[inline(always)]
+    pub fn contains_point(&self, a: VecN<T, D>) -> bool {
+        self.ranges
+            .iter()
+            .zip(a.coords.iter())
+            .all(|(r, &p)| r.contains(p))
+    }
+
+    ///Subdivides the bounding box along the specified axis.
+    ///No floating point calculations are done.
+    ///Like `Rect::subdivide`, a point on the divider is inside both halves.
+    #[inline(always)]
+    pub fn subdivide(&self, axis: usize, divider: T) -> (AABB<T, D>, AABB<T, D>) {
+        let (l, r) = self.ranges[axis].subdivide(divider);
+
+        let mut left = *self;
+        let mut right = *self;
+        left.ranges[axis] = l;
+        right.ranges[axis] = r;
+        (left, right)
+    }
+
+    ///Returns true if the bounding box's ranges are not degenerate.
+    #[inline(always)]
+    pub fn is_valid(&self) -> bool {
+        self.ranges.iter().all(|r| r.is_valid())
+    }
+
+    ///Returns true if the specified bounding box is inside of this one.
+    #[inline(always)]
+    pub fn contains_rect(&self, rect: &AABB<T, D>) -> bool {
+        self.ranges
+            .iter()
+            .zip(rect.ranges.iter())
+            .all(|(a, b)| a.contains_range(b))
+    }
+
+    #[inline(always)]
+    pub fn intersects_rect(&self, other: &AABB<T, D>) -> bool {
+        self.ranges
+            .iter()
+            .zip(other.ranges.iter())
+            .all(|(a, b)| b.intersects(a))
+    }
+
+    #[inline(always)]
+    pub fn grow_to_fit_point(&mut self, point: VecN<T, D>) -> &mut Self {
+        for (r, &p) in self.ranges.iter_mut().zip(point.coords.iter()) {
+            r.grow_to_fit(&Range::new(p, p));
+        }
+        self
+    }
+
+    ///Grow the bounding box to fit the specified bounding box by replacing values
+    ///with the specified one. No floating point computations.
+    #[inline(always)]
+    pub fn grow_to_fit(&mut self, rect: &AABB<T, D>) -> &mut Self {
+        for (a, b) in self.ranges.iter_mut().zip(rect.ranges.iter()) {
+            a.grow_to_fit(b);
+        }
+        self
+    }
+
+    ///Get an intersecting bounding box.
+    ///Follows the same rules as `Rect::get_intersect_rect`.
+    #[inline(always)]
+    pub fn get_intersect_rect(&self, other: &AABB<T, D>) -> Option<AABB<T, D>> {
+        let mut ranges = self.ranges;
+        for (a, b) in ranges.iter_mut().zip(other.ranges.iter()) {
+            *a = a.get_intersection(b)?;
+        }
+        Some(aabb(ranges))
+    }
+}
+
+impl<B> From<Rect<B>> for AABB<B, 2> {
+    #[inline(always)]
+    fn from(a: Rect<B>) -> Self {
+        aabb([a.x, a.y])
+    }
+}
+
+impl<B> From<AABB<B, 2>> for Rect<B> {
+    #[inline(always)]
+    fn from(a: AABB<B, 2>) -> Self {
+        let [x, y] = a.ranges;
+        Rect { x, y }
+    }
+}
+
+impl<B> From<Box3<B>> for AABB<B, 3> {
+    #[inline(always)]
+    fn from(a: Box3<B>) -> Self {
+        aabb([a.x, a.y, a.z])
+    }
+}
+
+impl<B> From<AABB<B, 3>> for Box3<B> {
+    #[inline(always)]
+    fn from(a: AABB<B, 3>) -> Self {
+        let [x, y, z] = a.ranges;
+        Box3 { x, y, z }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect_roundtrip() {
+        let r = rect(0, 10, 5, 20);
+        let a: AABB<_, 2> = r.into();
+        assert_eq!(Rect::from(a), r);
+
+        let (l, rr) = a.subdivide(1, 8);
+        assert_eq!(Rect::from(l), r.subdivide(YAXIS, 8).0);
+        assert_eq!(Rect::from(rr), r.subdivide(YAXIS, 8).1);
+
+        let b = aabb([range(5, 15), range(0, 10)]);
+        assert_eq!(
+            a.get_intersect_rect(&b).map(Rect::from),
+            r.get_intersect_rect(&Rect::from(b))
+        );
+    }
+
+    #[test]
+    fn test_generic_dimension() {
+        fn volume<const D: usize>(a: &AABB<i32, D>) -> i32 {
+            a.ranges.iter().map(|r| r.distance()).product()
+        }
+
+        let mut a = aabb([range(0, 1), range(0, 2), range(0, 3), range(0, 4)]);
+        assert_eq!(volume(&a), 24);
+
+        a.grow_to_fit_point(vecn([2, 0, 0, 0]));
+        assert_eq!(volume(&a), 48);
+        assert!(a.contains_point(vecn([2, 2, 3, 4])));
+        assert!(!a.contains_point(vecn([2, 2, 3, 5])));
+        assert!(a.intersects_rect(&aabb([range(2, 3); 4])));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::range::Range;
+use crate::vec3::vec3;
+use crate::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+///Convenience function to create a Box3.
+#[inline(always)]
+pub fn box3<T>(xstart: T, xend: T, ystart: T, yend: T, zstart: T, zend: T) -> Box3<T> {
+    Box3 {
+        x: Range::new(xstart, xend),
+        y: Range::new(ystart, yend),
+        z: Range::new(zstart, zend),
+    }
+}
+
+///An axis aligned box. Stored as three Ranges.
+///The 3D counterpart of `Rect`.
+#[derive(Default, Hash, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub struct Box3<T> {
+    pub x: Range<T>,
+    pub y: Range<T>,
+    pub z: Range<T>,
+}
+
+impl<T> Box3<T> {
+    ///Get the range of one axis.
+    #[inline(always)]
+    pub fn get_range(&self, axis: impl Axis3) -> &Range<T> {
+        match axis.to_dyn3() {
+            AxisDyn3::X => &self.x,
+            AxisDyn3::Y => &self.y,
+            AxisDyn3::Z => &self.z,
+        }
+    }
+
+    ///Get the mutable range of one axis.
+    #[inline(always)]
+    pub fn get_range_mut(&mut self, axis: impl Axis3) -> &mut Range<T> {
+        match axis.to_dyn3() {
+            AxisDyn3::X => &mut self.x,
+            AxisDyn3::Y => &mut self.y,
+            AxisDyn3::Z => &mut self.z,
+        }
+    }
+}
+
+impl<T: Copy> Box3<T> {
+    #[inline(always)]
+    pub fn inner_as<B: 'static + Copy>(&self) -> Box3<B>
+    where
+        T: num_traits::AsPrimitive<B>,
+    {
+        Box3 {
+            x: self.x.inner_as(),
+            y: self.y.inner_as(),
+            z: self.z.inner_as(),
+        }
+    }
+}
+
+impl<T: Copy + core::ops::Sub<Output = T> + core::ops::Add<Output = T>> Box3<T> {
+    ///Create a box from a point and radius.
+    #[inline(always)]
+    pub fn from_point(point: Vec3<T>, radius: Vec3<T>) -> Box3<T> {
+        Box3 {
+            x: Range::from_point(point.x, radius.x),
+            y: Range::from_point(point.y, radius.y),
+            z: Range::from_point(point.z, radius.z),
+        }
+    }
+
+    ///Grow a box of a radius.
+    #[inline(always)]
+    pub fn grow(self, radius: T) -> Self {
+        Box3 {
+            x: self.x.grow(radius),
+            y: self.y.grow(radius),
+            z: self.z.grow(radius),
+        }
+    }
+}
+
+impl<T: num_traits::Num + Copy> Box3<T> {
+    #[inline(always)]
+    pub fn derive_center(&self) -> Vec3<T> {
+        let two = T::one() + T::one();
+        let half = |r: &Range<T>| r.start + (r.end - r.start) / two;
+        vec3(half(&self.x), half(&self.y), half(&self.z))
+    }
+}
+
+impl<T: PartialOrd + Copy> Box3<T> {
+    ///Returns true if the point is contained in the the ranges of all axis.
+    #[inline(always)]
+    pub fn contains_point(&self, a: Vec3<T>) -> bool {
+        self.x.contains(a.x) && self.y.contains(a.y) && self.z.contains(a.z)
+    }
+
+    ///Subdivides the box along the specified axis.
+    ///No floating point calculations are done.
+    ///Like `Rect::subdivide`, a point on the divider is inside both halves.
+    #[inline(always)]
+    pub fn subdivide<A: Axis3>(&self, axis: A, divider: T) -> (Box3<T>, Box3<T>) {
+        let (l, r) = self.get_range(axis).subdivide(divider);
+
+        let mut left = *self;
+        let mut right = *self;
+        *left.get_range_mut(axis) = l;
+        *right.get_range_mut(axis) = r;
+        (left, right)
+    }
+
+    ///Returns true if the box's ranges are not degenerate.
+    #[inline(always)]
+    pub fn is_valid(&self) -> bool {
+        self.x.is_valid() && self.y.is_valid() && self.z.is_valid()
+    }
+
+    ///Returns true if the specified box is inside of this box.
+    #[inline(always)]
+    pub fn contains_box(&self, other: &Box3<T>) -> bool {
+        self.x.contains_range(&other.x)
+            && self.y.contains_range(&other.y)
+            && self.z.contains_range(&other.z)
+    }
+
+    #[inline(always)]
+    pub fn intersects_box(&self, other: &Box3<T>) -> bool {
+        other.x.intersects(&self.x) && other.y.intersects(&self.y) && other.z.intersects(&self.z)
+    }
+
+    ///Grow the box to fit the specified box by replacing values
+    ///with the specified box. No floating point computations.
+    #[inline(always)]
+    pub fn grow_to_fit(&mut self, other: &Box3<T>) -> &mut Self {
+        self.x.grow_to_fit(&other.x);
+        self.y.grow_to_fit(&other.y);
+        self.z.grow_to_fit(&other.z);
+        self
+    }
+
+    #[inline(always)]
+    pub fn grow_to_fit_point(&mut self, point: Vec3<T>) -> &mut Self {
+        self.x.grow_to_fit(&Range::new(point.x, point.x));
+        self.y.grow_to_fit(&Range::new(point.y, point.y));
+        self.z.grow_to_fit(&Range::new(point.z, point.z));
+        self
+    }
+
+    ///Get an intersecting box.
+    ///Follows the same rules as `Rect::get_intersect_rect`.
+    #[inline(always)]
+    pub fn get_intersect_box(&self, other: &Box3<T>) -> Option<Box3<T>> {
+        let x = self.x.get_intersection(&other.x)?;
+        let y = self.y.get_intersection(&other.y)?;
+        let z = self.z.get_intersection(&other.z)?;
+        Some(Box3 { x, y, z })
+    }
+}
@@ -0,0 +1,430 @@
+use crate::*;
+use alloc::vec::Vec;
+
+///Nodes with this many items or fewer are not split any further.
+const LEAF_SIZE: usize = 4;
+
+struct Node<N> {
+    //The smallest rect that contains every item under this node.
+    rect: Rect<N>,
+    kind: NodeKind,
+}
+
+enum NodeKind {
+    Leaf { start: usize, end: usize },
+    Inner { left: usize, right: usize },
+}
+
+///A bounding volume hierarchy of items with bounding rects.
+///
+///Built top down by choosing the split with the lowest surface area heuristic cost,
+///where the surface area of a rect is its perimeter.
+///If the items move, the tree can be updated with `refit`.
+#[must_use]
+pub struct Bvh<T: HasRect> {
+    nodes: Vec<Node<T::Num>>,
+    items: Vec<T>,
+}
+
+impl<T: HasRect> Bvh<T> {
+    ///The items in the tree. They are reordered when the tree is built.
+    #[inline(always)]
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    ///Mutable access to the items. Call `refit` after changing their rects.
+    #[inline(always)]
+    pub fn items_mut(&mut self) -> &mut [T] {
+        &mut self.items
+    }
+
+    #[inline(always)]
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+fn union<'a, N: PartialOrd + Copy + 'a>(rects: impl IntoIterator<Item = &'a Rect<N>>) -> Rect<N> {
+    Rect::union_all(rects.into_iter().copied()).unwrap()
+}
+
+///Half of the perimeter, which is proportional to the surface area heuristic cost of a 2d rect.
+#[inline(always)]
+fn half_perimeter<N: num_traits::Num + Copy>(rect: &Rect<N>) -> N {
+    rect.x.distance() + rect.y.distance()
+}
+
+fn sort_by_center<A: Axis, T: HasRect>(axis: A, items: &mut [T])
+where
+    T::Num: num_traits::Num + PartialOrd + Copy,
+{
+    //Comparing start+end avoids a division to find the center.
+    let key = |a: &T| {
+        let r = a.get_rect().get_range(axis);
+        r.start + r.end
+    };
+    items.sort_unstable_by(|a, b| {
+        key(a)
+            .partial_cmp(&key(b))
+            .unwrap_or(core::cmp::Ordering::Equal)
+    });
+}
+
+///Returns the lowest cost of splitting the items sorted along the axis and how many items go left.
+fn best_split<A: Axis, T: HasRect>(axis: A, items: &mut [T]) -> (T::Num, usize)
+where
+    T::Num: num_traits::Num + PartialOrd + Copy + 'static,
+    usize: num_traits::AsPrimitive<T::Num>,
+{
+    use num_traits::AsPrimitive;
+
+    sort_by_center(axis, items);
+
+    //The bounding rect of every suffix of the items.
+    let mut right_rects: Vec<_> = items.iter().rev().map(|a| *a.get_rect()).collect();
+    for i in 1..right_rects.len() {
+        let prev = right_rects[i - 1];
+        right_rects[i].grow_to_fit(&prev);
+    }
+    right_rects.reverse();
+
+    let mut best = None;
+    let mut left = *items[0].get_rect();
+    for i in 1..items.len() {
+        let right = &right_rects[i];
+        let cost =
+            half_perimeter(&left) * i.as_() + half_perimeter(right) * (items.len() - i).as_();
+        match best {
+            Some((c, _)) if c <= cost => {}
+            _ => best = Some((cost, i)),
+        }
+        left.grow_to_fit(items[i].get_rect());
+    }
+    best.unwrap()
+}
+
+fn build<T: HasRect>(nodes: &mut Vec<Node<T::Num>>, items: &mut [T], offset: usize) -> usize
+where
+    T::Num: num_traits::Num + PartialOrd + Copy + 'static,
+    usize: num_traits::AsPrimitive<T::Num>,
+{
+    let index = nodes.len();
+    let rect = union(items.iter().map(|a| a.get_rect()));
+
+    if items.len() <= LEAF_SIZE {
+        nodes.push(Node {
+            rect,
+            kind: NodeKind::Leaf {
+                start: offset,
+                end: offset + items.len(),
+            },
+        });
+        return index;
+    }
+
+    let (xcost, xsplit) = best_split(XAXIS, items);
+    let (ycost, ysplit) = best_split(YAXIS, items);
+    let split = if xcost < ycost {
+        sort_by_center(XAXIS, items);
+        xsplit
+    } else {
+        //The items are already sorted along y.
+        ysplit
+    };
+
+    //Reserve this node's spot so the children come after it.
+    nodes.push(Node {
+        rect,
+        kind: NodeKind::Leaf { start: 0, end: 0 },
+    });
+    let (l, r) = items.split_at_mut(split);
+    let left = build(nodes, l, offset);
+    let right = build(nodes, r, offset + split);
+    nodes[index].kind = NodeKind::Inner { left, right };
+    index
+}
+
+impl<T: HasRect> Bvh<T>
+where
+    T::Num: num_traits::Num + PartialOrd + Copy + 'static,
+    usize: num_traits::AsPrimitive<T::Num>,
+{
+    pub fn new(mut items: Vec<T>) -> Bvh<T> {
+        let mut nodes = Vec::new();
+        if !items.is_empty() {
+            build(&mut nodes, &mut items, 0);
+        }
+        Bvh { nodes, items }
+    }
+}
+
+impl<T: HasRect> Bvh<T>
+where
+    T::Num: PartialOrd + Copy,
+{
+    ///Recalculates the rects of every node after the items have moved.
+    ///The structure of the tree is kept, so queries get slower if the items move far.
+    pub fn refit(&mut self) {
+        //Children are always after their parent, so go backwards.
+        for i in (0..self.nodes.len()).rev() {
+            let rect = match self.nodes[i].kind {
+                NodeKind::Leaf { start, end } => {
+                    union(self.items[start..end].iter().map(|a| a.get_rect()))
+                }
+                NodeKind::Inner { left, right } => {
+                    union([&self.nodes[left].rect, &self.nodes[right].rect])
+                }
+            };
+            self.nodes[i].rect = rect;
+        }
+    }
+
+    ///Calls the function on every item whose rect intersects the specified rect.
+    pub fn query_rect(&self, rect: &Rect<T::Num>, mut func: impl FnMut(&T)) {
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.rect.intersects_rect(rect) {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Leaf { start, end } => {
+                    for a in self.items[start..end].iter() {
+                        if a.get_rect().intersects_rect(rect) {
+                            func(a);
+                        }
+                    }
+                }
+                NodeKind::Inner { left, right } => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+            }
+        }
+    }
+}
+
+impl<T: HasRect> Bvh<T>
+where
+    T::Num: Copy
+        + PartialOrd
+        + num_traits::Zero
+        + core::ops::Sub<Output = T::Num>
+        + core::ops::Mul<Output = T::Num>
+        + core::ops::Add<Output = T::Num>,
+{
+    ///Returns the item closest to the point along with its squared distance.
+    ///Items that contain the point have a distance of zero.
+    pub fn nearest(&self, point: Vec2<T::Num>) -> Option<(&T, T::Num)> {
+        let root = self.nodes.first()?;
+
+        let dis = |r: &Rect<T::Num>| {
+            r.distance_squared_to_point(point)
+                .unwrap_or_else(num_traits::Zero::zero)
+        };
+
+        //Every item is closer than the furthest corner of a node that contains it,
+        //so the closest item is no further than the smallest of these seen so far.
+        let is_closer = |best: &Option<(&T, T::Num)>, d: T::Num| match best {
+            Some((_, b)) => d < *b,
+            None => true,
+        };
+
+        let mut bound = root.rect.furthest_distance_squared_to_point(point);
+        let mut best: Option<(&T, T::Num)> = None;
+
+        let mut stack = alloc::vec![0];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if dis(&node.rect) > bound {
+                continue;
+            }
+            let far = node.rect.furthest_distance_squared_to_point(point);
+            if far < bound {
+                bound = far;
+            }
+
+            match node.kind {
+                NodeKind::Leaf { start, end } => {
+                    for a in self.items[start..end].iter() {
+                        let d = dis(a.get_rect());
+                        if is_closer(&best, d) {
+                            best = Some((a, d));
+                            if d < bound {
+                                bound = d;
+                            }
+                        }
+                    }
+                }
+                NodeKind::Inner { left, right } => {
+                    //Visit the closer child first.
+                    if dis(&self.nodes[left].rect) < dis(&self.nodes[right].rect) {
+                        stack.push(right);
+                        stack.push(left);
+                    } else {
+                        stack.push(left);
+                        stack.push(right);
+                    }
+                }
+            }
+        }
+        best
+    }
+}
+
+impl<T: HasRect> Bvh<T>
+where
+    T::Num: num_traits::Num + num_traits::Signed + PartialOrd + Copy + core::fmt::Debug,
+{
+    ///Returns the first item the ray hits along with the t value of the hit.
+    pub fn cast_ray(&self, ray: &Ray<T::Num>) -> Option<(&T, T::Num)> {
+        let mut best: Option<(&T, T::Num)> = None;
+        let is_closer = |best: &Option<(&T, T::Num)>, t: T::Num| match best {
+            Some((_, b)) => t < *b,
+            None => true,
+        };
+
+        let mut stack = Vec::new();
+        if let Some(root) = self.nodes.first() {
+            if let CastResult::Hit(t) = ray.cast_to_rect(&root.rect) {
+                stack.push((0, t));
+            }
+        }
+
+        while let Some((index, t)) = stack.pop() {
+            if !is_closer(&best, t) {
+                continue;
+            }
+            match self.nodes[index].kind {
+                NodeKind::Leaf { start, end } => {
+                    for a in self.items[start..end].iter() {
+                        if let CastResult::Hit(t) = ray.cast_to_rect(a.get_rect()) {
+                            if is_closer(&best, t) {
+                                best = Some((a, t));
+                            }
+                        }
+                    }
+                }
+                NodeKind::Inner { left, right } => {
+                    let l = ray.cast_to_rect(&self.nodes[left].rect);
+                    let r = ray.cast_to_rect(&self.nodes[right].rect);
+
+                    //Push the further child first so the closer one is visited first.
+                    match (l, r) {
+                        (CastResult::Hit(lt), CastResult::Hit(rt)) => {
+                            if lt < rt {
+                                stack.push((right, rt));
+                                stack.push((left, lt));
+                            } else {
+                                stack.push((left, lt));
+                                stack.push((right, rt));
+                            }
+                        }
+                        (CastResult::Hit(lt), CastResult::NoHit) => stack.push((left, lt)),
+                        (CastResult::NoHit, CastResult::Hit(rt)) => stack.push((right, rt)),
+                        (CastResult::NoHit, CastResult::NoHit) => {}
+                    }
+                }
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn scattered() -> Vec<(Rect<i32>, usize)> {
+        (0..60)
+            .map(|i| {
+                let x = (i * 37 % 100) as i32;
+                let y = (i * 61 % 100) as i32;
+                (rect(x, x + 4, y, y + 4), i)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bvh_query_rect() {
+        let bvh = Bvh::new(scattered());
+        let query = rect(20, 60, 30, 70);
+
+        let mut found = vec![];
+        bvh.query_rect(&query, |a| found.push(a.1));
+        found.sort_unstable();
+
+        let expected: Vec<_> = scattered()
+            .into_iter()
+            .filter(|a| a.0.intersects_rect(&query))
+            .map(|a| a.1)
+            .collect();
+        assert_eq!(found, expected);
+        assert!(!found.is_empty());
+    }
+
+    #[test]
+    fn test_bvh_nearest_and_refit() {
+        let mut bvh = Bvh::new(scattered());
+
+        for p in [vec2(0, 0), vec2(50, 50), vec2(99, 3), vec2(-20, 130)] {
+            let (_, d) = bvh.nearest(p).unwrap();
+            let expected = scattered()
+                .iter()
+                .map(|a| a.0.distance_squared_to_point(p).unwrap_or(0))
+                .min()
+                .unwrap();
+            assert_eq!(d, expected);
+        }
+
+        //Move one item far away and check the tree still finds it.
+        let item = bvh.items_mut().iter_mut().find(|a| a.1 == 7).unwrap();
+        item.0 = rect(500, 504, 500, 504);
+        bvh.refit();
+        let (a, d) = bvh.nearest(vec2(510, 502)).unwrap();
+        assert_eq!((a.1, d), (7, 36));
+
+        let mut found = vec![];
+        bvh.query_rect(&rect(490, 510, 490, 510), |a| found.push(a.1));
+        assert_eq!(found, vec![7]);
+    }
+
+    #[test]
+    fn test_bvh_ray() {
+        let bvh = Bvh::new(vec![
+            rect(10.0, 20.0, -5.0, 5.0),
+            rect(30.0, 40.0, -5.0, 5.0),
+            rect(-20.0, -10.0, -5.0, 5.0),
+            rect(15.0, 25.0, 10.0, 20.0),
+            rect(0.0, 5.0, 6.0, 9.0),
+            rect(50.0, 60.0, -1.0, 1.0),
+        ]);
+
+        let (a, t) = bvh.cast_ray(&ray(vec2(0.0, 0.0), vec2(1.0, 0.0))).unwrap();
+        assert_eq!((*a, t), (rect(10.0, 20.0, -5.0, 5.0), 10.0));
+
+        let (a, t) = bvh.cast_ray(&ray(vec2(0.0, 0.0), vec2(-1.0, 0.0))).unwrap();
+        assert_eq!((*a, t), (rect(-20.0, -10.0, -5.0, 5.0), 10.0));
+
+        assert!(bvh
+            .cast_ray(&ray(vec2(0.0, 30.0), vec2(1.0, 0.0)))
+            .is_none());
+    }
+}
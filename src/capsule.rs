@@ -0,0 +1,248 @@
+use crate::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+///Convenience function to create a Capsule.
+#[inline(always)]
+pub fn capsule<N>(a: Vec2<N>, b: Vec2<N>, radius: N) -> Capsule<N> {
+    Capsule { a, b, radius }
+}
+
+///Every point within `radius` of the segment from a to b.
+///This is the shape a circle covers when it moves from a to b.
+///Points on the edge of the capsule are considered inside of it.
+#[derive(Default, Hash, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub struct Capsule<N> {
+    pub a: Vec2<N>,
+    pub b: Vec2<N>,
+    pub radius: N,
+}
+
+impl<N: Copy> Capsule<N> {
+    #[inline(always)]
+    pub fn inner_as<B: 'static + Copy>(&self) -> Capsule<B>
+    where
+        N: num_traits::AsPrimitive<B>,
+    {
+        capsule(self.a.inner_as(), self.b.inner_as(), self.radius.as_())
+    }
+
+    ///The segment that runs through the middle of the capsule.
+    #[inline(always)]
+    pub fn segment(&self) -> Segment<N> {
+        segment(self.a, self.b)
+    }
+}
+
+impl<N: PartialOrd + Copy + core::ops::Sub<Output = N> + core::ops::Add<Output = N>> Capsule<N> {
+    ///Returns the smallest rect that contains the capsule.
+    #[inline(always)]
+    pub fn bounding_rect(&self) -> Rect<N> {
+        self.segment().bounding_rect().grow(self.radius)
+    }
+}
+
+///Returns true if the squared distance from the point to the segment is at most `rr`.
+///This compares the squared distance as a fraction without dividing, so it is exact for integers.
+fn segment_within<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy>(
+    s: &Segment<N>,
+    point: Vec2<N>,
+    rr: N,
+) -> bool {
+    let r = s.b - s.a;
+    let offset = point - s.a;
+    let num = offset.dot(r);
+    let den = r.magnitude2();
+
+    if num <= N::zero() {
+        offset.magnitude2() <= rr
+    } else if num >= den {
+        (point - s.b).magnitude2() <= rr
+    } else {
+        //The squared distance to the line is `cross * cross / den`.
+        let cross = r.cross(offset);
+        cross * cross <= rr * den
+    }
+}
+
+///Returns true if the squared distance between the closest points of two segments is at most `rr`.
+fn segments_within<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy>(
+    s1: &Segment<N>,
+    s2: &Segment<N>,
+    rr: N,
+) -> bool {
+    if s1.intersect(s2) != SegmentIntersection::NoIntersection {
+        return true;
+    }
+
+    //Segments that don't cross are closest at one of their end points.
+    segment_within(s1, s2.a, rr)
+        || segment_within(s1, s2.b, rr)
+        || segment_within(s2, s1.a, rr)
+        || segment_within(s2, s1.b, rr)
+}
+
+///Returns true if the squared distance between the closest points of a segment and a rect is at most `rr`.
+fn rect_within<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy>(
+    s: &Segment<N>,
+    rect: &Rect<N>,
+    rr: N,
+) -> bool {
+    if s.intersects_rect(rect) {
+        return true;
+    }
+
+    //If they don't touch, they are closest at an end point of the segment or a corner of the rect.
+    let near_rect = |p: Vec2<N>| rect.distance_squared_to_point(p).unwrap_or_else(N::zero) <= rr;
+    rect.get_corners().iter().any(|&p| segment_within(s, p, rr)) || near_rect(s.a) || near_rect(s.b)
+}
+
+impl<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy> Capsule<N> {
+    ///Returns the squared distance from the point to the segment through the middle of the capsule.
+    ///For integers, the closest point on the segment is truncated, so this is approximate.
+    ///`contains_point` and the intersection tests are exact.
+    #[inline(always)]
+    #[must_use]
+    pub fn segment_distance_squared_to_point(&self, point: Vec2<N>) -> N {
+        self.segment().distance_squared_to_point(point)
+    }
+
+    #[inline(always)]
+    pub fn contains_point(&self, point: Vec2<N>) -> bool {
+        segment_within(&self.segment(), point, self.radius * self.radius)
+    }
+
+    ///Returns true if the capsules touch.
+    #[inline(always)]
+    pub fn intersects_capsule(&self, other: &Capsule<N>) -> bool {
+        let rr = self.radius + other.radius;
+        segments_within(&self.segment(), &other.segment(), rr * rr)
+    }
+
+    ///Returns true if the capsule touches the rect.
+    #[inline(always)]
+    pub fn intersects_rect(&self, rect: &Rect<N>) -> bool {
+        rect_within(&self.segment(), rect, self.radius * self.radius)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<N: num_traits::Float + num_traits::Signed> Capsule<N> {
+    ///Returns the distance from the point to the edge of the capsule.
+    ///The distance is negative if the point is inside of the capsule.
+    #[inline(always)]
+    #[must_use]
+    pub fn distance_to_point(&self, point: Vec2<N>) -> N {
+        self.segment_distance_squared_to_point(point).sqrt() - self.radius
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<N: num_traits::Float + num_traits::Signed> Ray<N> {
+    ///Checks if a ray intersects a capsule.
+    ///The ray is cast against the circles at each end and the two straight sides.
+    ///If the ray starts inside the capsule, the tval is zero.
+    pub fn cast_to_capsule(&self, capsule: &Capsule<N>) -> CastResult<N> {
+        if capsule.contains_point(self.point) {
+            return CastResult::Hit(N::zero());
+        }
+
+        let closest = |a: CastResult<N>, b: CastResult<N>| match (a, b) {
+            (CastResult::Hit(a), CastResult::Hit(b)) if b < a => CastResult::Hit(b),
+            (CastResult::NoHit, b) => b,
+            (a, _) => a,
+        };
+
+        let ends = closest(
+            self.cast_to_circle(&circle(capsule.a, capsule.radius)),
+            self.cast_to_circle(&circle(capsule.b, capsule.radius)),
+        );
+
+        let dir = capsule.b - capsule.a;
+        if dir.magnitude2() == N::zero() {
+            return ends;
+        }
+        let offset = dir.rotate_90deg_right().normalize_to(capsule.radius);
+        let side = |offset: Vec2<N>| {
+            self.cast_to_segment(&segment(capsule.a + offset, capsule.b + offset))
+        };
+        closest(closest(ends, side(offset)), side(-offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capsule() {
+        let cap = capsule(vec2(0, 0), vec2(10, 0), 2);
+        assert_eq!(cap.bounding_rect(), rect(-2, 12, -2, 2));
+
+        assert!(cap.contains_point(vec2(5, 2)));
+        assert!(cap.contains_point(vec2(-1, 1)));
+        assert!(!cap.contains_point(vec2(-2, 1)));
+        assert_eq!(cap.segment_distance_squared_to_point(vec2(13, 4)), 25);
+
+        assert!(cap.intersects_capsule(&capsule(vec2(5, 4), vec2(5, 10), 2)));
+        assert!(!cap.intersects_capsule(&capsule(vec2(5, 5), vec2(5, 10), 2)));
+        //Crossing segments.
+        assert!(cap.intersects_capsule(&capsule(vec2(5, -5), vec2(5, 5), 0)));
+        //Capsules with no length are circles.
+        let dot = capsule(vec2(0, 0), vec2(0, 0), 1);
+        assert!(!dot.intersects_capsule(&capsule(vec2(5, 0), vec2(5, 0), 1)));
+        assert!(dot.intersects_capsule(&capsule(vec2(2, 0), vec2(2, 0), 1)));
+
+        assert!(cap.intersects_rect(&rect(3, 6, 2, 8)));
+        assert!(!cap.intersects_rect(&rect(3, 6, 3, 8)));
+        //Only touches the rounded end.
+        assert!(cap.intersects_rect(&rect(11, 15, 1, 5)));
+        assert!(!cap.intersects_rect(&rect(12, 15, 2, 5)));
+        assert!(cap.intersects_rect(&rect(-10, 20, -10, 20)));
+
+        //The closest point on the segment to (3,3) is not an integer.
+        //Its squared distance is 4.05, so it is just outside of the capsule.
+        let cap = capsule(vec2(0, 0), vec2(10, 3), 2);
+        assert!(!cap.contains_point(vec2(3, 3)));
+        assert!(cap.contains_point(vec2(5, 3)));
+        assert!(!cap.intersects_capsule(&capsule(vec2(3, 3), vec2(3, 3), 0)));
+        assert!(!cap.intersects_rect(&rect(2, 3, 3, 4)));
+    }
+
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[test]
+    fn test_cast_to_capsule() {
+        let cap = capsule(vec2(0.0, 0.0), vec2(10.0, 0.0), 2.0);
+        assert_eq!(cap.distance_to_point(vec2(5.0, 5.0)), 3.0);
+        assert_eq!(cap.distance_to_point(vec2(5.0, 0.0)), -2.0);
+
+        assert_eq!(
+            ray(vec2(5.0, 10.0), vec2(0.0, -1.0)).cast_to_capsule(&cap),
+            CastResult::Hit(8.0)
+        );
+        assert_eq!(
+            ray(vec2(-10.0, 0.0), vec2(1.0, 0.0)).cast_to_capsule(&cap),
+            CastResult::Hit(8.0)
+        );
+        assert_eq!(
+            ray(vec2(15.0, 0.0), vec2(-1.0, 0.0)).cast_to_capsule(&cap),
+            CastResult::Hit(3.0)
+        );
+        assert_eq!(
+            ray(vec2(5.0, 1.0), vec2(1.0, 0.0)).cast_to_capsule(&cap),
+            CastResult::Hit(0.0)
+        );
+        assert_eq!(
+            ray(vec2(-10.0, 3.0), vec2(1.0, 0.0)).cast_to_capsule(&cap),
+            CastResult::NoHit
+        );
+
+        let dot = capsule(vec2(0.0, 0.0), vec2(0.0, 0.0), 1.0);
+        assert_eq!(
+            ray(vec2(-5.0, 0.0), vec2(1.0, 0.0)).cast_to_capsule(&dot),
+            CastResult::Hit(4.0)
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use crate::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+///Convenience function to create a Circle.
+#[inline(always)]
+pub fn circle<N>(center: Vec2<N>, radius: N) -> Circle<N> {
+    Circle { center, radius }
+}
+
+///A circle. Points on the edge of the circle are considered inside of it.
+#[derive(Default, Hash, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub struct Circle<N> {
+    pub center: Vec2<N>,
+    pub radius: N,
+}
+
+impl<N: Copy> Circle<N> {
+    #[inline(always)]
+    pub fn inner_as<B: 'static + Copy>(&self) -> Circle<B>
+    where
+        N: num_traits::AsPrimitive<B>,
+    {
+        circle(self.center.inner_as(), self.radius.as_())
+    }
+}
+
+impl<N: Copy + core::ops::Sub<Output = N> + core::ops::Add<Output = N>> Circle<N> {
+    ///Returns the smallest rect that contains the circle.
+    #[inline(always)]
+    pub fn bounding_rect(&self) -> Rect<N> {
+        Rect::from_point(self.center, vec2same(self.radius))
+    }
+}
+
+impl<
+        N: Copy
+            + PartialOrd
+            + core::ops::Sub<Output = N>
+            + core::ops::Mul<Output = N>
+            + core::ops::Add<Output = N>,
+    > Circle<N>
+{
+    ///Returns true if the point is inside of the circle.
+    #[inline(always)]
+    pub fn contains_point(&self, point: Vec2<N>) -> bool {
+        self.center.distance_squared_to_point(point) <= self.radius * self.radius
+    }
+
+    ///Returns true if the specified rect is completely inside of the circle.
+    #[inline(always)]
+    pub fn contains_rect(&self, rect: &Rect<N>) -> bool {
+        rect.furthest_distance_squared_to_point(self.center) <= self.radius * self.radius
+    }
+
+    ///Returns true if the specified circle is completely inside of this circle.
+    #[inline(always)]
+    pub fn contains_circle(&self, other: &Circle<N>) -> bool {
+        if other.radius > self.radius {
+            return false;
+        }
+        let diff = self.radius - other.radius;
+        self.center.distance_squared_to_point(other.center) <= diff * diff
+    }
+
+    ///Returns true if the circle touches the rect.
+    #[inline(always)]
+    pub fn intersects_rect(&self, rect: &Rect<N>) -> bool {
+        match rect.distance_squared_to_point(self.center) {
+            Some(dis) => dis <= self.radius * self.radius,
+            None => true,
+        }
+    }
+
+    ///Returns true if the two circles touch.
+    #[inline(always)]
+    pub fn intersects_circle(&self, other: &Circle<N>) -> bool {
+        let rr = self.radius + other.radius;
+        self.center.distance_squared_to_point(other.center) <= rr * rr
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<N: num_traits::Float> Circle<N> {
+    ///If the circles overlap, returns how far they would have to be moved apart
+    ///along the line between their centers to only be touching.
+    #[inline(always)]
+    pub fn penetration_depth(&self, other: &Circle<N>) -> Option<N> {
+        let dis = (other.center - self.center).magnitude();
+        let depth = self.radius + other.radius - dis;
+        if depth >= N::zero() {
+            Some(depth)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circle() {
+        let c = circle(vec2(0, 0), 5);
+        assert_eq!(c.bounding_rect(), rect(-5, 5, -5, 5));
+
+        assert!(c.contains_point(vec2(3, 4)));
+        assert!(!c.contains_point(vec2(4, 4)));
+
+        assert!(c.intersects_rect(&rect(5, 10, -1, 1)));
+        assert!(c.intersects_rect(&rect(-1, 1, -1, 1)));
+        assert!(!c.intersects_rect(&rect(4, 10, 4, 10)));
+        assert!(c.contains_rect(&rect(-3, 3, -4, 4)));
+        assert!(!c.contains_rect(&rect(-3, 4, -4, 4)));
+
+        assert!(c.intersects_circle(&circle(vec2(10, 0), 5)));
+        assert!(!c.intersects_circle(&circle(vec2(10, 1), 5)));
+        assert!(c.contains_circle(&circle(vec2(1, 0), 4)));
+        assert!(!c.contains_circle(&circle(vec2(1, 0), 5)));
+    }
+
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[test]
+    fn test_circle_float() {
+        let a = circle(vec2(0.0f32, 0.0), 2.0);
+        let b = circle(vec2(3.0f32, 0.0), 2.0);
+        assert_eq!(a.penetration_depth(&b), Some(1.0));
+        assert_eq!(a.penetration_depth(&circle(vec2(5.0, 0.0), 1.0)), None);
+
+        let r = ray(vec2(-5.0f32, 0.0), vec2(1.0, 0.0));
+        assert_eq!(r.cast_to_circle(&a), CastResult::Hit(3.0));
+    }
+}
@@ -0,0 +1,42 @@
+//! Compact array representations for use with `#[serde(with = "...")]`.
+//!
+//! By default the types in this crate serialize with named fields.
+//! These modules instead store them as plain arrays, matching the `From` conversions
+//! to arrays, e.g. `[xstart,xend,ystart,yend]` for a `Rect`.
+//!
+//! ```
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Level {
+//!     #[serde(with = "axgeom::compact::rect")]
+//!     bounds: axgeom::Rect<i32>,
+//! }
+//! ```
+
+macro_rules! compact_mod {
+    ($name:ident, $ty:ident, $len:expr) => {
+        pub mod $name {
+            use crate::$ty;
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            pub fn serialize<S: Serializer, T: Serialize + Copy>(
+                a: &$ty<T>,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                let arr: [T; $len] = (*a).into();
+                arr.serialize(serializer)
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+                deserializer: D,
+            ) -> Result<$ty<T>, D::Error> {
+                let arr = <[T; $len]>::deserialize(deserializer)?;
+                Ok(arr.into())
+            }
+        }
+    };
+}
+
+compact_mod!(range, Range, 2);
+compact_mod!(rect, Rect, 4);
+compact_mod!(vec2, Vec2, 2);
+compact_mod!(vec3, Vec3, 3);
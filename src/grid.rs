@@ -0,0 +1,335 @@
+use crate::range::Range;
+use crate::ray::num_cells;
+use crate::*;
+use alloc::vec::Vec;
+use num_traits::AsPrimitive;
+
+///A uniform grid that partitions a bounding rect into cells of a fixed size.
+///Items are stored in every cell that their rect touches.
+///Parts of items that are outside of the bounds are not found by queries.
+///
+///Cells are addressed by their column and row, starting at the top left of the bounds.
+///If the bounds are not a multiple of the cell size, the last column and row are smaller.
+#[must_use]
+pub struct Grid<T: HasRect> {
+    bounds: Rect<T::Num>,
+    cell_size: Vec2<T::Num>,
+    dim: Vec2<usize>,
+    cells: Vec<Vec<usize>>,
+    items: Vec<Option<T>>,
+    free: Vec<usize>,
+}
+
+///Iterates over every cell in the specified range of columns and rows.
+fn cells_in(cells: Rect<usize>) -> impl Iterator<Item = Vec2<usize>> {
+    (cells.y.start..=cells.y.end)
+        .flat_map(move |y| (cells.x.start..=cells.x.end).map(move |x| vec2(x, y)))
+}
+
+impl<T: HasRect> Grid<T> {
+    ///The number of columns and rows.
+    #[inline(always)]
+    pub fn dim(&self) -> Vec2<usize> {
+        self.dim
+    }
+
+    #[inline(always)]
+    pub fn bounds(&self) -> &Rect<T::Num> {
+        &self.bounds
+    }
+
+    #[inline(always)]
+    pub fn get(&self, key: usize) -> Option<&T> {
+        self.items.get(key)?.as_ref()
+    }
+
+    ///The keys of the items in a cell.
+    #[inline(always)]
+    pub fn cell_items(&self, cell: Vec2<usize>) -> &[usize] {
+        &self.cells[cell.y * self.dim.x + cell.x]
+    }
+}
+
+impl<T: HasRect> Grid<T>
+where
+    T::Num: PartialOrd + Copy + 'static + num_traits::Num + AsPrimitive<usize>,
+    usize: AsPrimitive<T::Num>,
+{
+    ///Creates a grid with enough cells of the specified size to cover the bounds.
+    ///Panics if the cell size is not positive.
+    pub fn new(bounds: Rect<T::Num>, cell_size: Vec2<T::Num>) -> Grid<T> {
+        let zero = num_traits::zero::<T::Num>();
+        assert!(
+            cell_size.x > zero && cell_size.y > zero,
+            "the cell size of a grid must be positive"
+        );
+        let dim = vec2(
+            num_cells(&bounds.x, cell_size.x),
+            num_cells(&bounds.y, cell_size.y),
+        );
+
+        Grid {
+            bounds,
+            cell_size,
+            dim,
+            cells: (0..dim.x * dim.y).map(|_| Vec::new()).collect(),
+            items: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    #[inline(always)]
+    fn cell_index<A: Axis>(&self, axis: A, val: T::Num) -> usize {
+        let start = self.bounds.get_range(axis).start;
+        let i: usize = ((val - start) / *self.cell_size.get_axis(axis)).as_();
+        //A value on the far edge of the bounds is in the last cell.
+        let last = *self.dim.get_axis(axis) - 1;
+        if i > last {
+            last
+        } else {
+            i
+        }
+    }
+
+    ///Returns the column and row of the cell that contains the point.
+    ///Returns None if the point is outside of the bounds.
+    pub fn cell_of_point(&self, point: Vec2<T::Num>) -> Option<Vec2<usize>> {
+        if !self.bounds.contains_point(point) {
+            return None;
+        }
+        Some(vec2(
+            self.cell_index(XAXIS, point.x),
+            self.cell_index(YAXIS, point.y),
+        ))
+    }
+
+    ///Returns the range of columns and rows of the cells that the rect touches.
+    ///Returns None if the rect is outside of the bounds.
+    pub fn cells_of_rect(&self, rect: &Rect<T::Num>) -> Option<Rect<usize>> {
+        let rect = rect.get_intersect_rect(&self.bounds)?;
+        Some(Rect {
+            x: Range::new(
+                self.cell_index(XAXIS, rect.x.start),
+                self.cell_index(XAXIS, rect.x.end),
+            ),
+            y: Range::new(
+                self.cell_index(YAXIS, rect.y.start),
+                self.cell_index(YAXIS, rect.y.end),
+            ),
+        })
+    }
+
+    ///Returns the rect of the cell at the specified column and row.
+    pub fn cell_rect(&self, cell: Vec2<usize>) -> Rect<T::Num> {
+        let mut rect = self.bounds;
+
+        //Cut the bounds at the edges of the cell.
+        macro_rules! macro_axis {
+            ($axis:ident) => {{
+                let size = *self.cell_size.get_axis($axis);
+                let start = rect.get_range($axis).start + cell.get_axis($axis).as_() * size;
+                let end = start + size;
+                if start > rect.get_range($axis).start {
+                    rect = rect.subdivide($axis, start).1;
+                }
+                if end < rect.get_range($axis).end {
+                    rect = rect.subdivide($axis, end).0;
+                }
+            }};
+        }
+
+        macro_axis!(XAXIS);
+        macro_axis!(YAXIS);
+        rect
+    }
+
+    ///Adds an item to every cell that its rect touches.
+    ///Returns a key that can be used to remove the item.
+    pub fn insert(&mut self, item: T) -> usize {
+        let cells = self.cells_of_rect(item.get_rect());
+        let key = match self.free.pop() {
+            Some(key) => {
+                self.items[key] = Some(item);
+                key
+            }
+            None => {
+                self.items.push(Some(item));
+                self.items.len() - 1
+            }
+        };
+
+        if let Some(cells) = cells {
+            for c in cells_in(cells) {
+                let index = c.y * self.dim.x + c.x;
+                self.cells[index].push(key);
+            }
+        }
+        key
+    }
+
+    ///Removes the item with the specified key.
+    pub fn remove(&mut self, key: usize) -> Option<T> {
+        let item = self.items.get_mut(key)?.take()?;
+        if let Some(cells) = self.cells_of_rect(item.get_rect()) {
+            for c in cells_in(cells) {
+                let index = c.y * self.dim.x + c.x;
+                self.cells[index].retain(|&k| k != key);
+            }
+        }
+        self.free.push(key);
+        Some(item)
+    }
+
+    ///Calls the function on every item whose rect intersects the specified rect.
+    ///Each item is visited once even if it is in many cells.
+    pub fn query_rect(&self, rect: &Rect<T::Num>, mut func: impl FnMut(usize, &T)) {
+        let cells = match self.cells_of_rect(rect) {
+            Some(cells) => cells,
+            None => return,
+        };
+
+        for c in cells_in(cells) {
+            for &key in self.cell_items(c) {
+                let item = self.items[key].as_ref().unwrap();
+                let item_rect = item.get_rect();
+                if !item_rect.intersects_rect(rect) {
+                    continue;
+                }
+
+                //Only visit the item from the first cell that it shares with the query.
+                let item_cells = self.cells_of_rect(item_rect).unwrap();
+                let first = vec2(
+                    item_cells.x.start.max(cells.x.start),
+                    item_cells.y.start.max(cells.y.start),
+                );
+                if first == c {
+                    func(key, item);
+                }
+            }
+        }
+    }
+}
+
+impl<T: HasRect> Grid<T>
+where
+    T::Num: num_traits::float::FloatCore
+        + num_traits::Signed
+        + core::fmt::Debug
+        + 'static
+        + AsPrimitive<usize>
+        + AsPrimitive<isize>,
+    usize: AsPrimitive<T::Num>,
+    isize: AsPrimitive<T::Num>,
+{
+    ///Calls the function on every item the ray hits, along with the t value of the hit.
+    ///The items are in no particular order.
+    pub fn query_ray(&self, ray: &Ray<T::Num>, mut func: impl FnMut(usize, &T, T::Num)) {
+        let mut keys = Vec::new();
+        for (cell, _, _) in ray.grid_traverse(
+            self.cell_size,
+            &self.bounds,
+            num_traits::float::FloatCore::infinity(),
+        ) {
+            keys.extend_from_slice(self.cell_items(cell.inner_as::<usize>()));
+        }
+        keys.sort_unstable();
+        keys.dedup();
+
+        for key in keys {
+            let item = self.items[key].as_ref().unwrap();
+            if let CastResult::Hit(t) = ray.cast_to_rect(item.get_rect()) {
+                func(key, item, t);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_grid_cells() {
+        let grid: Grid<Rect<i32>> = Grid::new(rect(0, 25, 0, 20), vec2(10, 10));
+        assert_eq!(grid.dim(), vec2(3, 2));
+
+        assert_eq!(grid.cell_of_point(vec2(15, 5)), Some(vec2(1, 0)));
+        assert_eq!(grid.cell_of_point(vec2(25, 20)), Some(vec2(2, 1)));
+        assert_eq!(grid.cell_of_point(vec2(26, 5)), None);
+
+        assert_eq!(grid.cell_rect(vec2(1, 1)), rect(10, 20, 10, 20));
+        assert_eq!(grid.cell_rect(vec2(2, 0)), rect(20, 25, 0, 10));
+
+        assert_eq!(
+            grid.cells_of_rect(&rect(-5, 12, 5, 8)),
+            Some(rect(0, 1, 0, 0))
+        );
+        assert_eq!(grid.cells_of_rect(&rect(30, 40, 5, 8)), None);
+    }
+
+    #[test]
+    fn test_grid_query() {
+        let mut grid = Grid::new(rect(0, 100, 0, 100), vec2(10, 10));
+        let a = grid.insert((rect(5, 35, 5, 15), 'a'));
+        let b = grid.insert((rect(50, 55, 50, 55), 'b'));
+        let c = grid.insert((rect(90, 200, 90, 200), 'c'));
+
+        let mut found = vec![];
+        grid.query_rect(&rect(0, 60, 0, 60), |_, item| found.push(item.1));
+        found.sort_unstable();
+        assert_eq!(found, vec!['a', 'b']);
+
+        assert_eq!(grid.remove(a).map(|a| a.1), Some('a'));
+        assert_eq!(grid.remove(a), None);
+        let mut found = vec![];
+        grid.query_rect(&rect(0, 100, 0, 100), |k, _| found.push(k));
+        found.sort_unstable();
+        assert_eq!(found, vec![b, c]);
+
+        //The key of a removed item is reused.
+        assert_eq!(grid.insert((rect(0, 1, 0, 1), 'd')), a);
+    }
+
+    #[test]
+    #[should_panic(expected = "the cell size of a grid must be positive")]
+    fn test_grid_zero_cell_size() {
+        let _: Grid<Rect<i32>> = Grid::new(rect(0, 10, 0, 10), vec2(10, 0));
+    }
+
+    #[test]
+    fn test_grid_ray() {
+        let mut grid = Grid::new(rect(0.0, 100.0, 0.0, 100.0), vec2(10.0, 10.0));
+        grid.insert(rect(20.0, 30.0, 40.0, 60.0));
+        grid.insert(rect(60.0, 70.0, 50.0, 80.0));
+        grid.insert(rect(60.0, 70.0, 0.0, 10.0));
+
+        let mut hits = vec![];
+        grid.query_ray(&ray(vec2(-10.0, 55.0), vec2(1.0, 0.0)), |k, _, t| {
+            hits.push((k, t))
+        });
+        hits.sort_unstable_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        assert_eq!(hits, vec![(0, 30.0), (1, 70.0)]);
+    }
+
+    #[test]
+    fn test_grid_ray_inexact() {
+        //Neither size is an exact multiple of the cell size once rounded,
+        //so the grid and the traversal have to agree on the number of cells.
+        let mut grid = Grid::new(rect(0.0, 0.07, 0.0, 0.07), vec2(0.01, 0.01));
+        let key = grid.insert(rect(0.065, 0.07, 0.065, 0.07));
+        let mut hits = vec![];
+        grid.query_ray(&ray(vec2(-1.0, 0.069), vec2(1.0, 0.0)), |k, _, _| {
+            hits.push(k)
+        });
+        assert_eq!(hits, vec![key]);
+
+        let (bounds, cell_size) = (rect(0.0, 0.11, 0.0, 0.11), vec2(0.011, 0.011));
+        let grid: Grid<Rect<f64>> = Grid::new(bounds, cell_size);
+        let (last, _, _) = ray(vec2(-1.0, 0.05), vec2(1.0, 0.0))
+            .grid_traverse(cell_size, &bounds, f64::INFINITY)
+            .last()
+            .unwrap();
+        assert_eq!(last.x as usize, grid.dim().x - 1);
+    }
+}
@@ -0,0 +1,334 @@
+use crate::*;
+use alloc::vec::Vec;
+
+///Nodes with this many items or fewer are not split any further.
+const LEAF_SIZE: usize = 8;
+
+struct Node<N> {
+    //Every item in this node and its children is inside of this rect.
+    rect: Rect<N>,
+    //The items that belong to this node.
+    start: usize,
+    end: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+///A kd-tree of items with bounding rects.
+///
+///Each level splits its rect at the median start of its items, alternating between the x and y axis.
+///Items that cross the divider stay at the node that split them.
+#[must_use]
+pub struct KdTree<T: HasRect> {
+    nodes: Vec<Node<T::Num>>,
+    items: Vec<T>,
+}
+
+///Moves the items that satisfy the predicate to the front and returns how many there are.
+fn partition<T>(items: &mut [T], mut func: impl FnMut(&T) -> bool) -> usize {
+    let mut count = 0;
+    for i in 0..items.len() {
+        if func(&items[i]) {
+            items.swap(count, i);
+            count += 1;
+        }
+    }
+    count
+}
+
+fn build<A: Axis, T: HasRect>(
+    axis: A,
+    nodes: &mut Vec<Node<T::Num>>,
+    items: &mut [T],
+    offset: usize,
+    rect: Rect<T::Num>,
+) where
+    T::Num: PartialOrd + Copy,
+{
+    let index = nodes.len();
+    nodes.push(Node {
+        rect,
+        start: offset,
+        end: offset + items.len(),
+        left: None,
+        right: None,
+    });
+
+    if items.len() <= LEAF_SIZE {
+        return;
+    }
+
+    let med = items.len() / 2;
+    items.select_nth_unstable_by(med, |a, b| {
+        let a = a.get_rect().get_range(axis).start;
+        let b = b.get_rect().get_range(axis).start;
+        a.partial_cmp(&b).unwrap_or(core::cmp::Ordering::Equal)
+    });
+    let divider = items[med].get_rect().get_range(axis).start;
+
+    let is_left = |a: &T| a.get_rect().get_range(axis).end <= divider;
+    let is_right = |a: &T| a.get_rect().get_range(axis).start >= divider;
+
+    //Order the items as [middle, left, right] so that each node's items are contiguous.
+    let num_mid = partition(items, |a| !is_left(a) && !is_right(a));
+    let (mid, rest) = items.split_at_mut(num_mid);
+    let num_left = partition(rest, |a| is_left(a));
+    let (left, right) = rest.split_at_mut(num_left);
+
+    //Nothing would be gained by splitting.
+    if mid.is_empty() && (left.is_empty() || right.is_empty()) {
+        return;
+    }
+
+    nodes[index].end = offset + num_mid;
+
+    let (lrect, rrect) = rect.subdivide(axis, divider);
+    if !left.is_empty() {
+        nodes[index].left = Some(nodes.len());
+        build(axis.next(), nodes, left, offset + num_mid, lrect);
+    }
+    if !right.is_empty() {
+        nodes[index].right = Some(nodes.len());
+        build(
+            axis.next(),
+            nodes,
+            right,
+            offset + num_mid + num_left,
+            rrect,
+        );
+    }
+}
+
+impl<T: HasRect> KdTree<T> {
+    ///The items in the tree. They are reordered when the tree is built.
+    #[inline(always)]
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    #[inline(always)]
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    #[inline(always)]
+    fn node_items(&self, node: &Node<T::Num>) -> &[T] {
+        &self.items[node.start..node.end]
+    }
+}
+
+impl<T: HasRect> KdTree<T>
+where
+    T::Num: PartialOrd + Copy,
+{
+    pub fn new(mut items: Vec<T>) -> KdTree<T> {
+        let mut nodes = Vec::new();
+        if let Some(rect) = Rect::union_all(items.iter().map(|a| *a.get_rect())) {
+            build(XAXIS, &mut nodes, &mut items, 0, rect);
+        }
+        KdTree { nodes, items }
+    }
+
+    ///Calls the function on every item whose rect intersects the specified rect.
+    pub fn query_rect(&self, rect: &Rect<T::Num>, mut func: impl FnMut(&T)) {
+        if !self.nodes.is_empty() {
+            self.query_rect_node(0, rect, &mut func);
+        }
+    }
+
+    fn query_rect_node(&self, index: usize, rect: &Rect<T::Num>, func: &mut impl FnMut(&T)) {
+        let node = &self.nodes[index];
+        if !node.rect.intersects_rect(rect) {
+            return;
+        }
+        for a in self.node_items(node) {
+            if a.get_rect().intersects_rect(rect) {
+                func(a);
+            }
+        }
+        for child in node.left.iter().chain(node.right.iter()) {
+            self.query_rect_node(*child, rect, func);
+        }
+    }
+}
+
+impl<T: HasRect> KdTree<T>
+where
+    T::Num: Copy
+        + PartialOrd
+        + num_traits::Zero
+        + core::ops::Sub<Output = T::Num>
+        + core::ops::Mul<Output = T::Num>
+        + core::ops::Add<Output = T::Num>,
+{
+    ///Returns the k items closest to the point along with their squared distance, closest first.
+    ///Items that contain the point have a distance of zero.
+    pub fn k_nearest(&self, point: Vec2<T::Num>, k: usize) -> Vec<(&T, T::Num)> {
+        let mut res = Vec::with_capacity(k);
+        if k > 0 && !self.nodes.is_empty() {
+            self.k_nearest_node(0, point, k, &mut res);
+        }
+        res
+    }
+
+    fn k_nearest_node<'a>(
+        &'a self,
+        index: usize,
+        point: Vec2<T::Num>,
+        k: usize,
+        res: &mut Vec<(&'a T, T::Num)>,
+    ) {
+        let dis = |r: &Rect<T::Num>| {
+            r.distance_squared_to_point(point)
+                .unwrap_or_else(num_traits::Zero::zero)
+        };
+        let is_full = |res: &Vec<(&T, T::Num)>, d: T::Num| res.len() == k && d > res[k - 1].1;
+
+        let node = &self.nodes[index];
+        if is_full(res, dis(&node.rect)) {
+            return;
+        }
+
+        for a in self.node_items(node) {
+            let d = dis(a.get_rect());
+            if !is_full(res, d) {
+                let pos = res.partition_point(|b| b.1 <= d);
+                res.insert(pos, (a, d));
+                res.truncate(k);
+            }
+        }
+
+        //Visit the closer child first so that more of the other child is pruned.
+        let (first, second) = match (node.left, node.right) {
+            (Some(l), Some(r)) if dis(&self.nodes[r].rect) < dis(&self.nodes[l].rect) => {
+                (Some(r), Some(l))
+            }
+            (l, r) => (l, r),
+        };
+        for child in first.iter().chain(second.iter()) {
+            self.k_nearest_node(*child, point, k, res);
+        }
+    }
+}
+
+impl<T: HasRect> KdTree<T>
+where
+    T::Num: num_traits::Num + num_traits::Signed + PartialOrd + Copy + core::fmt::Debug,
+{
+    ///Returns the first item the ray hits along with the t value of the hit.
+    pub fn cast_ray(&self, ray: &Ray<T::Num>) -> Option<(&T, T::Num)> {
+        let mut best = None;
+        if !self.nodes.is_empty() {
+            self.cast_ray_node(0, ray, &mut best);
+        }
+        best
+    }
+
+    fn cast_ray_node<'a>(
+        &'a self,
+        index: usize,
+        ray: &Ray<T::Num>,
+        best: &mut Option<(&'a T, T::Num)>,
+    ) {
+        let is_closer = |best: &Option<(&T, T::Num)>, t: T::Num| match best {
+            Some((_, b)) => t < *b,
+            None => true,
+        };
+
+        let node = &self.nodes[index];
+        match ray.cast_to_rect(&node.rect) {
+            CastResult::Hit(t) if is_closer(best, t) => {}
+            _ => return,
+        }
+
+        for a in self.node_items(node) {
+            if let CastResult::Hit(t) = ray.cast_to_rect(a.get_rect()) {
+                if is_closer(best, t) {
+                    *best = Some((a, t));
+                }
+            }
+        }
+        for child in node.left.iter().chain(node.right.iter()) {
+            self.cast_ray_node(*child, ray, best);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn grid() -> Vec<(Rect<i32>, usize)> {
+        (0..100)
+            .map(|i| {
+                let x = (i % 10) * 10;
+                let y = (i / 10) * 10;
+                (rect(x, x + 5, y, y + 5), i as usize)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_kdtree_query_rect() {
+        let tree = KdTree::new(grid());
+        assert_eq!(tree.len(), 100);
+
+        let query = rect(12, 33, 0, 17);
+        let mut found = Vec::new();
+        tree.query_rect(&query, |a| found.push(a.1));
+        found.sort_unstable();
+
+        let mut expected: Vec<_> = grid()
+            .into_iter()
+            .filter(|a| a.0.intersects_rect(&query))
+            .map(|a| a.1)
+            .collect();
+        expected.sort_unstable();
+        assert_eq!(found, expected);
+        assert_eq!(found, vec![1, 2, 3, 11, 12, 13]);
+    }
+
+    #[test]
+    fn test_kdtree_k_nearest() {
+        let tree = KdTree::new(grid());
+
+        let res = tree.k_nearest(vec2(52, 52), 1);
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].0 .1, 55);
+        assert_eq!(res[0].1, 0);
+
+        let res = tree.k_nearest(vec2(57, 52), 3);
+        let ids: Vec<_> = res.iter().map(|a| a.0 .1).collect();
+        assert_eq!(ids[0], 55);
+        assert_eq!(res.iter().map(|a| a.1).collect::<Vec<_>>(), vec![4, 9, 53]);
+
+        assert_eq!(tree.k_nearest(vec2(0, 0), 1000).len(), 100);
+    }
+
+    #[test]
+    fn test_kdtree_ray() {
+        let tree = KdTree::new(grid());
+
+        let (a, t) = tree.cast_ray(&ray(vec2(-10, 22), vec2(1, 0))).unwrap();
+        assert_eq!(a.1, 20);
+        assert_eq!(t, 10);
+
+        assert!(tree.cast_ray(&ray(vec2(-10, 7), vec2(1, 0))).is_none());
+        assert!(KdTree::<Rect<i32>>::new(Vec::new())
+            .cast_ray(&ray(vec2(0, 0), vec2(1, 0)))
+            .is_none());
+    }
+}
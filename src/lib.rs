@@ -3,11 +3,43 @@
 //!
 
 #![no_std]
+#![allow(clippy::items_after_test_module)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod aabb;
+mod box3;
+#[cfg(feature = "alloc")]
+mod bvh;
+mod capsule;
+mod circle;
+#[cfg(feature = "alloc")]
+mod grid;
+#[cfg(feature = "alloc")]
+mod kdtree;
+#[cfg(feature = "serde")]
+pub mod compact;
+mod mat2;
+mod obb;
+mod polygon;
+mod predicates;
+#[cfg(feature = "alloc")]
+mod quadtree;
 mod range;
+mod range_set;
 mod ray;
 mod rect;
+#[cfg(feature = "alloc")]
+mod rect_region;
+mod segment;
+mod sweep;
+mod triangle;
+#[cfg(feature = "serde")]
+mod serde_array;
 mod vec2;
+mod vec3;
+mod vecn;
 
 #[cfg(feature = "std")]
 pub use roots;
@@ -15,21 +47,76 @@ pub use roots;
 pub use num_traits;
 pub use partial_min_max;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub use self::aabb::aabb;
+pub use self::aabb::AABB;
+pub use self::box3::box3;
+pub use self::box3::Box3;
+#[cfg(feature = "alloc")]
+pub use self::bvh::Bvh;
+pub use self::capsule::capsule;
+pub use self::capsule::Capsule;
+pub use self::circle::circle;
+pub use self::circle::Circle;
+#[cfg(feature = "alloc")]
+pub use self::grid::Grid;
+#[cfg(feature = "alloc")]
+pub use self::kdtree::KdTree;
+pub use self::mat2::Affine2;
+pub use self::mat2::Mat2;
+pub use self::obb::Obb;
+pub use self::polygon::ConvexPolygon;
+#[cfg(feature = "alloc")]
+pub use self::polygon::ConvexPolygonVec;
+pub use self::predicates::incircle;
+pub use self::predicates::orient2d;
+pub use self::predicates::Predicates;
+#[cfg(feature = "alloc")]
+pub use self::quadtree::QuadTree;
 pub use self::range::range;
+pub use self::range::Bound;
+pub use self::range::Closed;
+pub use self::range::HalfOpen;
+pub use self::range::Open;
 pub use self::range::Range;
+pub use self::range_set::ArrayRangeSet;
+#[cfg(feature = "alloc")]
+pub use self::range_set::RangeSet;
 pub use self::ray::ray;
 pub use self::ray::CastResult;
+pub use self::ray::GridTraverse;
 pub use self::ray::Ray;
+pub use self::ray::RayHit;
 pub use self::rect::rect;
+pub use self::rect::HasRect;
 pub use self::rect::Rect;
+#[cfg(feature = "alloc")]
+pub use self::rect_region::RectRegion;
+pub use self::segment::segment;
+pub use self::segment::Segment;
+pub use self::segment::SegmentIntersection;
+pub use self::sweep::sweep_and_prune;
+#[cfg(feature = "alloc")]
+pub use self::sweep::sweep_and_prune_pairs;
+pub use self::triangle::triangle;
+pub use self::triangle::Triangle;
 pub use self::vec2::arr2_as;
 pub use self::vec2::vec2;
 pub use self::vec2::vec2same;
 pub use self::vec2::Vec2;
+pub use self::vec3::vec3;
+pub use self::vec3::vec3same;
+pub use self::vec3::Vec3;
+pub use self::vecn::vecn;
+pub use self::vecn::VecN;
 
 ///The x axis implementation of the Axis
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct XAXIS;
+#[allow(unused_attributes)]
 impl Axis for XAXIS {
     type Next = YAXIS;
     #[inline(always)]
@@ -46,7 +133,9 @@ impl Axis for XAXIS {
 
 ///The y axis implementation of the Axis
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct YAXIS;
+#[allow(unused_attributes)]
 impl Axis for YAXIS {
     type Next = XAXIS;
 
@@ -65,6 +154,7 @@ impl Axis for YAXIS {
 
 ///A dynamic axis as opposed to a statically known one via `impl Axis`.
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[must_use]
 pub enum AxisDyn {
     X,
@@ -122,3 +212,95 @@ pub trait Axis: Sync + Send + Copy + Clone {
         self.to_dyn().is_equal_to(other.to_dyn())
     }
 }
+
+///The z axis implementation of the Axis3.
+///There is no 2D z axis, so this only implements `Axis3`.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZAXIS;
+
+impl Axis3 for XAXIS {
+    type Next3 = YAXIS;
+    #[inline(always)]
+    fn next3(&self) -> Self::Next3 {
+        YAXIS
+    }
+    #[inline(always)]
+    fn to_dyn3(&self) -> AxisDyn3 {
+        AxisDyn3::X
+    }
+}
+
+impl Axis3 for YAXIS {
+    type Next3 = ZAXIS;
+    #[inline(always)]
+    fn next3(&self) -> Self::Next3 {
+        ZAXIS
+    }
+    #[inline(always)]
+    fn to_dyn3(&self) -> AxisDyn3 {
+        AxisDyn3::Y
+    }
+}
+
+impl Axis3 for ZAXIS {
+    type Next3 = XAXIS;
+    #[inline(always)]
+    fn next3(&self) -> Self::Next3 {
+        XAXIS
+    }
+    #[inline(always)]
+    fn to_dyn3(&self) -> AxisDyn3 {
+        AxisDyn3::Z
+    }
+}
+
+///A dynamic 3D axis as opposed to a statically known one via `impl Axis3`.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub enum AxisDyn3 {
+    X,
+    Y,
+    Z,
+}
+impl AxisDyn3 {
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_equal_to(&self, other: AxisDyn3) -> bool {
+        use AxisDyn3::*;
+        matches!((self, other), (X, X) | (Y, Y) | (Z, Z))
+    }
+
+    #[inline(always)]
+    pub const fn next(&self) -> Self {
+        use AxisDyn3::*;
+        match self {
+            X => Y,
+            Y => Z,
+            Z => X,
+        }
+    }
+}
+
+///Axis3 trait is the 3D counterpart of `Axis`.
+///The X implementation of this trait's Next3 associated trait is the Y implementation.
+///The Y implementation of this trait's Next3 associated trait is the Z implementation.
+///The Z implementation of this trait's Next3 associated trait is the X implementation.
+///
+///The items are suffixed with 3 so that `XAXIS` and `YAXIS` can implement
+///both `Axis` and `Axis3` without calls to `next()` becoming ambiguous.
+pub trait Axis3: Sync + Send + Copy + Clone {
+    type Next3: Axis3;
+    #[must_use]
+    fn next3(&self) -> Self::Next3;
+
+    ///Convert a statically known axis into a dynamic one.
+    fn to_dyn3(&self) -> AxisDyn3;
+
+    #[inline(always)]
+    #[must_use]
+    fn is_equal_to3<B: Axis3>(&self, other: B) -> bool {
+        self.to_dyn3().is_equal_to(other.to_dyn3())
+    }
+}
@@ -0,0 +1,270 @@
+use crate::*;
+use core::ops::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+///A 2x2 matrix stored as its two columns.
+///The x column is where the matrix moves the x axis `(1,0)` to, and the y column is where it moves `(0,1)` to.
+#[derive(Default, Hash, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub struct Mat2<N> {
+    pub x: Vec2<N>,
+    pub y: Vec2<N>,
+}
+
+///A 2D affine transform. A linear transform followed by a translation.
+#[derive(Default, Hash, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub struct Affine2<N> {
+    pub matrix: Mat2<N>,
+    pub translation: Vec2<N>,
+}
+
+impl<N> Mat2<N> {
+    #[inline(always)]
+    pub const fn from_cols(x: Vec2<N>, y: Vec2<N>) -> Mat2<N> {
+        Mat2 { x, y }
+    }
+}
+
+impl<N: num_traits::Num + Copy> Mat2<N> {
+    #[inline(always)]
+    pub fn identity() -> Mat2<N> {
+        Mat2::scale(vec2same(N::one()))
+    }
+
+    ///Scales each axis by the specified amount.
+    #[inline(always)]
+    pub fn scale(scale: Vec2<N>) -> Mat2<N> {
+        Mat2::from_cols(vec2(scale.x, N::zero()), vec2(N::zero(), scale.y))
+    }
+
+    ///Shears the x axis by `shear.x` times y, and the y axis by `shear.y` times x.
+    #[inline(always)]
+    pub fn shear(shear: Vec2<N>) -> Mat2<N> {
+        Mat2::from_cols(vec2(N::one(), shear.y), vec2(shear.x, N::one()))
+    }
+
+    #[inline(always)]
+    pub fn transpose(&self) -> Mat2<N> {
+        Mat2::from_cols(vec2(self.x.x, self.y.x), vec2(self.x.y, self.y.y))
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn determinant(&self) -> N {
+        self.x.cross(self.y)
+    }
+
+    #[inline(always)]
+    pub fn transform_vector(&self, v: Vec2<N>) -> Vec2<N> {
+        self.x * v.x + self.y * v.y
+    }
+}
+
+impl<N: num_traits::Num + num_traits::Signed + Copy> Mat2<N> {
+    ///Returns the inverse of the matrix, or None if the determinant is zero.
+    ///For integers, the inverse is truncated.
+    pub fn inverse(&self) -> Option<Mat2<N>> {
+        let det = self.determinant();
+        if det == N::zero() {
+            return None;
+        }
+        Some(Mat2::from_cols(
+            vec2(self.y.y, -self.x.y) / det,
+            vec2(-self.y.x, self.x.x) / det,
+        ))
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<N: num_traits::Float> Mat2<N> {
+    ///A counter clockwise rotation in radians, when the y axis points up.
+    #[inline(always)]
+    pub fn rotation(angle: N) -> Mat2<N> {
+        let (sin, cos) = angle.sin_cos();
+        Mat2::from_cols(vec2(cos, sin), vec2(-sin, cos))
+    }
+}
+
+///Composes the matrices, so that the right matrix is applied first.
+impl<N: num_traits::Num + Copy> Mul for Mat2<N> {
+    type Output = Mat2<N>;
+    #[inline(always)]
+    fn mul(self, rhs: Mat2<N>) -> Mat2<N> {
+        Mat2::from_cols(self.transform_vector(rhs.x), self.transform_vector(rhs.y))
+    }
+}
+
+impl<N: num_traits::Num + Copy> Mul<Vec2<N>> for Mat2<N> {
+    type Output = Vec2<N>;
+    #[inline(always)]
+    fn mul(self, rhs: Vec2<N>) -> Vec2<N> {
+        self.transform_vector(rhs)
+    }
+}
+
+impl<N: num_traits::Num + Copy> Affine2<N> {
+    #[inline(always)]
+    pub fn from_mat2(matrix: Mat2<N>) -> Affine2<N> {
+        Affine2 {
+            matrix,
+            translation: vec2same(N::zero()),
+        }
+    }
+
+    #[inline(always)]
+    pub fn identity() -> Affine2<N> {
+        Affine2::from_mat2(Mat2::identity())
+    }
+
+    #[inline(always)]
+    pub fn translation(translation: Vec2<N>) -> Affine2<N> {
+        Affine2 {
+            matrix: Mat2::identity(),
+            translation,
+        }
+    }
+
+    ///Scales each axis by the specified amount about the origin.
+    #[inline(always)]
+    pub fn scale(scale: Vec2<N>) -> Affine2<N> {
+        Affine2::from_mat2(Mat2::scale(scale))
+    }
+
+    ///See `Mat2::shear`.
+    #[inline(always)]
+    pub fn shear(shear: Vec2<N>) -> Affine2<N> {
+        Affine2::from_mat2(Mat2::shear(shear))
+    }
+
+    ///Returns a transform that applies this transform and then the other one.
+    #[inline(always)]
+    pub fn then(&self, other: &Affine2<N>) -> Affine2<N> {
+        *other * *self
+    }
+
+    #[inline(always)]
+    pub fn transform_point(&self, point: Vec2<N>) -> Vec2<N> {
+        self.matrix.transform_vector(point) + self.translation
+    }
+
+    ///Transforms a direction, which is not affected by the translation.
+    #[inline(always)]
+    pub fn transform_vector(&self, v: Vec2<N>) -> Vec2<N> {
+        self.matrix.transform_vector(v)
+    }
+
+    ///Transforms the point and direction of the ray.
+    ///The direction is not normalized, so a tval on the new ray is at the
+    ///transformed point of the same tval on the old ray.
+    #[inline(always)]
+    pub fn transform_ray(&self, ray: &Ray<N>) -> Ray<N> {
+        crate::ray(
+            self.transform_point(ray.point),
+            self.transform_vector(ray.dir),
+        )
+    }
+}
+
+impl<N: num_traits::Num + PartialOrd + Copy> Affine2<N> {
+    ///Returns the smallest rect that contains the transformed corners of the rect.
+    pub fn transform_rect(&self, rect: &Rect<N>) -> Rect<N> {
+        let corners = rect.get_corners().map(|a| self.transform_point(a));
+        Rect::from_points(corners).unwrap()
+    }
+}
+
+impl<N: num_traits::Num + num_traits::Signed + Copy> Affine2<N> {
+    ///Returns the inverse of the transform, or None if it is not invertible.
+    ///For integers, the inverse is truncated.
+    pub fn inverse(&self) -> Option<Affine2<N>> {
+        let matrix = self.matrix.inverse()?;
+        Some(Affine2 {
+            matrix,
+            translation: -matrix.transform_vector(self.translation),
+        })
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<N: num_traits::Float> Affine2<N> {
+    ///See `Mat2::rotation`.
+    #[inline(always)]
+    pub fn rotation(angle: N) -> Affine2<N> {
+        Affine2::from_mat2(Mat2::rotation(angle))
+    }
+}
+
+///Composes the transforms, so that the right transform is applied first.
+impl<N: num_traits::Num + Copy> Mul for Affine2<N> {
+    type Output = Affine2<N>;
+    #[inline(always)]
+    fn mul(self, rhs: Affine2<N>) -> Affine2<N> {
+        Affine2 {
+            matrix: self.matrix * rhs.matrix,
+            translation: self.transform_point(rhs.translation),
+        }
+    }
+}
+
+impl<N: num_traits::Num + Copy> Mul<Vec2<N>> for Affine2<N> {
+    type Output = Vec2<N>;
+    #[inline(always)]
+    fn mul(self, rhs: Vec2<N>) -> Vec2<N> {
+        self.transform_point(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_affine() {
+        let a = Affine2::translation(vec2(10, 0)).then(&Affine2::scale(vec2(2, 3)));
+        assert_eq!(a.transform_point(vec2(1, 1)), vec2(22, 3));
+        assert_eq!(a.transform_vector(vec2(1, 1)), vec2(2, 3));
+        assert_eq!(a * vec2(1, 1), vec2(22, 3));
+
+        let b = Affine2::shear(vec2(1, 0));
+        assert_eq!(b.transform_point(vec2(2, 3)), vec2(5, 3));
+        assert_eq!(b.transform_rect(&rect(0, 2, 0, 3)), rect(0, 5, 0, 3));
+
+        //Integer inverses are exact when the determinant is one.
+        let c = Affine2::translation(vec2(10, 0)).then(&b);
+        let inv = c.inverse().unwrap();
+        assert_eq!(
+            inv.transform_point(c.transform_point(vec2(4, 7))),
+            vec2(4, 7)
+        );
+        assert_eq!(c * inv, Affine2::identity());
+        assert_eq!(Affine2::<i32>::scale(vec2(0, 1)).inverse(), None);
+    }
+
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[test]
+    fn test_rotation() {
+        let close = |a: Vec2<f64>, b: Vec2<f64>| (a - b).magnitude() < 1e-9;
+
+        let rot = Affine2::rotation(core::f64::consts::FRAC_PI_2);
+        assert!(close(rot.transform_point(vec2(1.0, 0.0)), vec2(0.0, 1.0)));
+
+        let r = rot.transform_rect(&rect(0.0, 2.0, 0.0, 1.0));
+        assert!(close(vec2(r.x.start, r.y.start), vec2(-1.0, 0.0)));
+        assert!(close(vec2(r.x.end, r.y.end), vec2(0.0, 2.0)));
+
+        let t = Affine2::translation(vec2(5.0, 0.0)).then(&rot);
+        let ray = t.transform_ray(&ray(vec2(0.0, 0.0), vec2(1.0, 0.0)));
+        assert!(close(ray.point, vec2(0.0, 5.0)));
+        assert!(close(ray.dir, vec2(0.0, 1.0)));
+
+        let back = t.inverse().unwrap();
+        assert!(close(
+            back.transform_point(t.transform_point(vec2(3.0, 4.0))),
+            vec2(3.0, 4.0)
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+///An oriented bounding box.
+///A rect centered at `center` with the specified half extents, rotated by `rotation` radians about its center.
+///Points on the edge of the box are considered inside of it.
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub struct Obb<N> {
+    pub center: Vec2<N>,
+    pub half_extents: Vec2<N>,
+    pub rotation: N,
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<N: num_traits::Float> Obb<N> {
+    ///Creates an unrotated box covering the rect.
+    #[inline(always)]
+    pub fn from_rect(rect: &Rect<N>) -> Obb<N> {
+        let two = N::one() + N::one();
+        Obb {
+            center: rect.derive_center(),
+            half_extents: vec2(rect.x.distance(), rect.y.distance()) / two,
+            rotation: N::zero(),
+        }
+    }
+
+    ///The box in its own space, where it is centered at the origin and not rotated.
+    #[inline(always)]
+    fn local_rect(&self) -> Rect<N> {
+        Rect::from_point(vec2same(N::zero()), self.half_extents)
+    }
+
+    ///The transform from the box's own space to world space.
+    #[inline(always)]
+    pub fn to_world(&self) -> Affine2<N> {
+        Affine2::rotation(self.rotation).then(&Affine2::translation(self.center))
+    }
+
+    ///The transform from world space to the box's own space.
+    #[inline(always)]
+    pub fn to_local(&self) -> Affine2<N> {
+        Affine2::translation(-self.center).then(&Affine2::rotation(-self.rotation))
+    }
+
+    ///The unit vectors along the box's own x and y axis.
+    #[inline(always)]
+    pub fn axes(&self) -> [Vec2<N>; 2] {
+        let m = Mat2::rotation(self.rotation);
+        [m.x, m.y]
+    }
+
+    ///Returns each corner in the same order as `Rect::get_corners`, before rotating:
+    ///topleft
+    ///topright
+    ///bottomright
+    ///bottomleft
+    pub fn get_corners(&self) -> [Vec2<N>; 4] {
+        let world = self.to_world();
+        self.local_rect()
+            .get_corners()
+            .map(|a| world.transform_point(a))
+    }
+
+    ///Returns the smallest rect that contains the box.
+    #[inline(always)]
+    pub fn bounding_rect(&self) -> Rect<N> {
+        self.to_world().transform_rect(&self.local_rect())
+    }
+
+    #[inline(always)]
+    pub fn contains_point(&self, point: Vec2<N>) -> bool {
+        self.local_rect()
+            .contains_point(self.to_local().transform_point(point))
+    }
+
+    ///The distance from the center to the furthest point of the box along the axis.
+    #[inline(always)]
+    fn project_radius(&self, axis: Vec2<N>) -> N {
+        let [ax, ay] = self.axes();
+        self.half_extents.x * ax.dot(axis).abs() + self.half_extents.y * ay.dot(axis).abs()
+    }
+
+    ///Returns true if the boxes touch, using the separating axis theorem.
+    pub fn intersects_obb(&self, other: &Obb<N>) -> bool {
+        let [a, b] = self.axes();
+        let [c, d] = other.axes();
+        let offset = other.center - self.center;
+
+        [a, b, c, d].iter().all(|&axis| {
+            offset.dot(axis).abs() <= self.project_radius(axis) + other.project_radius(axis)
+        })
+    }
+
+    ///Returns true if the box touches the rect.
+    #[inline(always)]
+    pub fn intersects_rect(&self, rect: &Rect<N>) -> bool {
+        self.intersects_obb(&Obb::from_rect(rect))
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<N: num_traits::Float + num_traits::Signed + core::fmt::Debug> Ray<N> {
+    ///Checks if a ray intersects an oriented box.
+    ///The ray is moved into the box's own space, which doesn't change its tvals.
+    #[inline(always)]
+    pub fn cast_to_obb(&self, obb: &Obb<N>) -> CastResult<N> {
+        obb.to_local()
+            .transform_ray(self)
+            .cast_to_rect(&obb.local_rect())
+    }
+}
+
+#[cfg(all(test, any(feature = "std", feature = "libm")))]
+mod tests {
+    use super::*;
+
+    fn close(a: Vec2<f64>, b: Vec2<f64>) -> bool {
+        (a - b).magnitude() < 1e-9
+    }
+
+    #[test]
+    fn test_obb() {
+        let quarter = core::f64::consts::FRAC_PI_4;
+        let obb = Obb {
+            center: vec2(10.0, 0.0),
+            half_extents: vec2(2.0f64.sqrt(), 2.0f64.sqrt()),
+            rotation: quarter,
+        };
+
+        let c = obb.get_corners();
+        assert!(close(c[0], vec2(10.0, -2.0)));
+        assert!(close(c[1], vec2(12.0, 0.0)));
+        assert!(close(c[2], vec2(10.0, 2.0)));
+        assert!(close(c[3], vec2(8.0, 0.0)));
+
+        let r = obb.bounding_rect();
+        assert!(close(vec2(r.x.start, r.y.start), vec2(8.0, -2.0)));
+        assert!(close(vec2(r.x.end, r.y.end), vec2(12.0, 2.0)));
+
+        assert!(obb.contains_point(vec2(11.0, 0.5)));
+        assert!(!obb.contains_point(vec2(11.5, 1.5)));
+
+        //Inside the bounding rect, but not the diamond.
+        assert!(!obb.intersects_rect(&rect(11.2, 13.0, 1.2, 3.0)));
+        assert!(obb.intersects_rect(&rect(10.5, 13.0, 0.5, 3.0)));
+
+        let other = Obb {
+            center: vec2(14.5, 0.0),
+            half_extents: vec2(2.0, 1.0),
+            rotation: 0.0,
+        };
+        assert!(!obb.intersects_obb(&other));
+        assert!(obb.intersects_obb(&Obb {
+            center: vec2(13.5, 0.0),
+            ..other
+        }));
+
+        let hit = ray(vec2(0.0, 0.0), vec2(1.0, 0.0)).cast_to_obb(&obb);
+        assert!((hit.unwrap() - 8.0).abs() < 1e-9);
+        assert_eq!(
+            ray(vec2(0.0, 2.5), vec2(1.0, 0.0)).cast_to_obb(&obb),
+            CastResult::NoHit
+        );
+    }
+}
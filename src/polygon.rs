@@ -0,0 +1,625 @@
+use crate::range::Range;
+use crate::*;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+//The functions below work on the points of any convex polygon,
+//so that both polygon types and `Ray::cast_to_polygon` can share them.
+
+///Returns every edge of the polygon, including the one from the last point back to the first.
+fn edges<N: Copy>(points: &[Vec2<N>]) -> impl Iterator<Item = (Vec2<N>, Vec2<N>)> + '_ {
+    let last = points.last().copied();
+    last.into_iter()
+        .chain(points.iter().copied())
+        .zip(points.iter().copied())
+}
+
+fn contains_point<N: num_traits::Num + PartialOrd + Copy>(
+    points: &[Vec2<N>],
+    point: Vec2<N>,
+) -> bool {
+    if points.len() < 3 {
+        return false;
+    }
+
+    //The point is inside if it is not on both sides of the edges.
+    let mut left = false;
+    let mut right = false;
+    for (a, b) in edges(points) {
+        let c = (b - a).cross(point - a);
+        if c > N::zero() {
+            left = true;
+        } else if c < N::zero() {
+            right = true;
+        }
+    }
+    !(left && right)
+}
+
+///Returns true if the polygons touch, using the separating axis theorem.
+pub(crate) fn intersects<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy>(
+    a: &[Vec2<N>],
+    b: &[Vec2<N>],
+) -> bool {
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+
+    let separated = |points: &[Vec2<N>]| {
+        edges(points).any(|(p, q)| {
+            let axis = (q - p).rotate_90deg_right();
+            let project =
+                |points: &[Vec2<N>]| Range::from_values(points.iter().map(|p| p.dot(axis)));
+            match (project(a), project(b)) {
+                (Some(x), Some(y)) => !x.intersects(&y),
+                _ => true,
+            }
+        })
+    };
+    !separated(a) && !separated(b)
+}
+
+///Twice the area of the polygon. Positive if the points are counter clockwise when the y axis points up.
+fn signed_area2<N: num_traits::Num + Copy>(points: &[Vec2<N>]) -> N {
+    edges(points).fold(N::zero(), |acc, (a, b)| acc + a.cross(b))
+}
+
+fn area<N: num_traits::Num + num_traits::Signed + Copy>(points: &[Vec2<N>]) -> N {
+    signed_area2(points).abs() / (N::one() + N::one())
+}
+
+fn centroid<N: num_traits::Num + Copy>(points: &[Vec2<N>]) -> Option<Vec2<N>> {
+    let area2 = signed_area2(points);
+    if area2 == N::zero() {
+        return None;
+    }
+    let sum = edges(points).fold(vec2same(N::zero()), |acc, (a, b)| {
+        acc + (a + b) * a.cross(b)
+    });
+    Some(sum / (area2 * (N::one() + N::one() + N::one())))
+}
+
+///One side of the rect that a stage of the clipper clips against.
+#[derive(Copy, Clone)]
+struct ClipSide<N> {
+    xaxis: bool,
+    line: N,
+    keep_greater: bool,
+}
+
+impl<N: num_traits::Num + PartialOrd + Copy> ClipSide<N> {
+    fn new(rect: &Rect<N>, stage: usize) -> ClipSide<N> {
+        let (xaxis, line, keep_greater) = match stage {
+            0 => (true, rect.x.start, true),
+            1 => (true, rect.x.end, false),
+            2 => (false, rect.y.start, true),
+            _ => (false, rect.y.end, false),
+        };
+        ClipSide {
+            xaxis,
+            line,
+            keep_greater,
+        }
+    }
+
+    #[inline(always)]
+    fn val(&self, p: Vec2<N>) -> N {
+        if self.xaxis {
+            p.x
+        } else {
+            p.y
+        }
+    }
+
+    #[inline(always)]
+    fn inside(&self, p: Vec2<N>) -> bool {
+        if self.keep_greater {
+            self.val(p) >= self.line
+        } else {
+            self.val(p) <= self.line
+        }
+    }
+
+    ///Returns where the segment crosses the side.
+    ///The points are on opposite sides, so they differ along the axis.
+    fn crossing(&self, a: Vec2<N>, b: Vec2<N>) -> Vec2<N> {
+        let mut p = a + (b - a) * (self.line - self.val(a)) / (self.val(b) - self.val(a));
+        if self.xaxis {
+            p.x = self.line;
+        } else {
+            p.y = self.line;
+        }
+        p
+    }
+}
+
+///The state of one stage of the clipper.
+#[derive(Copy, Clone)]
+struct ClipStage<N> {
+    first: Option<Vec2<N>>,
+    prev: Option<Vec2<N>>,
+}
+
+///Sutherland–Hodgman clipping against each side of a rect in turn.
+///The stages are chained so that points are passed on as soon as they are found,
+///and no intermediate polygons are stored.
+struct Clipper<'a, N, F> {
+    rect: &'a Rect<N>,
+    stages: [ClipStage<N>; 4],
+    first_out: Option<Vec2<N>>,
+    last_out: Option<Vec2<N>>,
+    func: F,
+}
+
+impl<'a, N: num_traits::Num + PartialOrd + Copy, F: FnMut(Vec2<N>)> Clipper<'a, N, F> {
+    fn push(&mut self, stage: usize, p: Vec2<N>) {
+        if stage == 4 {
+            self.output(p);
+            return;
+        }
+        match self.stages[stage].prev {
+            Some(prev) => self.edge(stage, prev, p),
+            None => self.stages[stage].first = Some(p),
+        }
+        self.stages[stage].prev = Some(p);
+    }
+
+    fn edge(&mut self, stage: usize, a: Vec2<N>, b: Vec2<N>) {
+        let side = ClipSide::new(self.rect, stage);
+        match (side.inside(a), side.inside(b)) {
+            (true, true) => self.push(stage + 1, b),
+            (true, false) => self.push(stage + 1, side.crossing(a, b)),
+            (false, true) => {
+                self.push(stage + 1, side.crossing(a, b));
+                self.push(stage + 1, b);
+            }
+            (false, false) => {}
+        }
+    }
+
+    ///Passes on the points, skipping ones that are the same as the point before them.
+    fn output(&mut self, p: Vec2<N>) {
+        match self.last_out {
+            Some(last) if last == p => {}
+            Some(last) => {
+                (self.func)(last);
+                self.first_out.get_or_insert(last);
+                self.last_out = Some(p);
+            }
+            None => self.last_out = Some(p),
+        }
+    }
+
+    fn finish(mut self) {
+        for stage in 0..4 {
+            if let ClipStage {
+                first: Some(first),
+                prev: Some(prev),
+            } = self.stages[stage]
+            {
+                self.edge(stage, prev, first);
+            }
+        }
+        if let Some(last) = self.last_out {
+            if Some(last) != self.first_out {
+                (self.func)(last);
+            }
+        }
+    }
+}
+
+fn clip_to_rect<N: num_traits::Num + PartialOrd + Copy>(
+    points: &[Vec2<N>],
+    rect: &Rect<N>,
+    func: impl FnMut(Vec2<N>),
+) {
+    let mut clipper = Clipper {
+        rect,
+        stages: [ClipStage {
+            first: None,
+            prev: None,
+        }; 4],
+        first_out: None,
+        last_out: None,
+        func,
+    };
+    for &p in points {
+        clipper.push(0, p);
+    }
+    clipper.finish();
+}
+
+///A convex polygon that can hold at most `K` points.
+///The points can be in either winding order.
+///Operations that would need more than `K` points return `None`.
+///
+///Polygons with fewer than three points have no area, and don't contain any points.
+///For integers, points made by clipping and the area and centroid are truncated.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "N: Serialize", deserialize = "N: Deserialize<'de>"))
+)]
+#[must_use]
+pub struct ConvexPolygon<N, const K: usize> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
+    points: [Vec2<N>; K],
+    len: usize,
+}
+
+impl<N: Default + Copy, const K: usize> Default for ConvexPolygon<N, K> {
+    #[inline(always)]
+    fn default() -> Self {
+        ConvexPolygon {
+            points: [Vec2::default(); K],
+            len: 0,
+        }
+    }
+}
+
+impl<N: core::hash::Hash, const K: usize> core::hash::Hash for ConvexPolygon<N, K> {
+    #[inline(always)]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.points().hash(state);
+    }
+}
+
+impl<N: PartialEq, const K: usize> PartialEq for ConvexPolygon<N, K> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.points() == other.points()
+    }
+}
+
+impl<N: Eq, const K: usize> Eq for ConvexPolygon<N, K> {}
+
+impl<N, const K: usize> AsRef<[Vec2<N>]> for ConvexPolygon<N, K> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[Vec2<N>] {
+        self.points()
+    }
+}
+
+impl<N, const K: usize> ConvexPolygon<N, K> {
+    #[inline(always)]
+    pub fn points(&self) -> &[Vec2<N>] {
+        &self.points[..self.len]
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<N: Default + Copy, const K: usize> ConvexPolygon<N, K> {
+    ///Creates a polygon with no points.
+    #[inline(always)]
+    pub fn new() -> ConvexPolygon<N, K> {
+        ConvexPolygon::default()
+    }
+
+    ///Creates a polygon from points that make a convex shape.
+    ///Returns `None` if there are more than `K` points.
+    pub fn from_points<I: IntoIterator<Item = Vec2<N>>>(points: I) -> Option<ConvexPolygon<N, K>> {
+        let mut poly = ConvexPolygon::default();
+        for p in points {
+            *poly.points.get_mut(poly.len)? = p;
+            poly.len += 1;
+        }
+        Some(poly)
+    }
+}
+
+impl<N: PartialOrd + Copy, const K: usize> ConvexPolygon<N, K> {
+    ///Returns the smallest rect that contains every point, or `None` if there are no points.
+    #[inline(always)]
+    pub fn bounding_rect(&self) -> Option<Rect<N>> {
+        Rect::from_points(self.points().iter().copied())
+    }
+}
+
+impl<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy, const K: usize>
+    ConvexPolygon<N, K>
+{
+    #[inline(always)]
+    pub fn contains_point(&self, point: Vec2<N>) -> bool {
+        contains_point(self.points(), point)
+    }
+
+    ///Returns true if the polygons touch, using the separating axis theorem.
+    #[inline(always)]
+    pub fn intersects_polygon(&self, other: &impl AsRef<[Vec2<N>]>) -> bool {
+        intersects(self.points(), other.as_ref())
+    }
+
+    ///Returns true if the polygon touches the rect.
+    #[inline(always)]
+    pub fn intersects_rect(&self, rect: &Rect<N>) -> bool {
+        intersects(self.points(), &rect.get_corners())
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn area(&self) -> N {
+        area(self.points())
+    }
+
+    ///Returns the center of mass of the polygon, or `None` if it has no area.
+    #[inline(always)]
+    pub fn centroid(&self) -> Option<Vec2<N>> {
+        centroid(self.points())
+    }
+}
+
+impl<N: num_traits::Num + PartialOrd + Default + Copy, const K: usize> ConvexPolygon<N, K> {
+    ///Returns the part of the polygon that is inside of the rect.
+    ///The result is empty if they don't touch.
+    ///Clipping can add up to four points, so returns `None` if the result has more than `K` points.
+    pub fn clip_to_rect(&self, rect: &Rect<N>) -> Option<ConvexPolygon<N, K>> {
+        let mut poly = ConvexPolygon::default();
+        let mut fits = true;
+        clip_to_rect(self.points(), rect, |p| {
+            match poly.points.get_mut(poly.len) {
+                Some(a) => {
+                    *a = p;
+                    poly.len += 1;
+                }
+                None => fits = false,
+            }
+        });
+        if fits {
+            Some(poly)
+        } else {
+            None
+        }
+    }
+}
+
+///A convex polygon with any number of points.
+///The points can be in either winding order.
+///
+///Polygons with fewer than three points have no area, and don't contain any points.
+///For integers, points made by clipping and the area and centroid are truncated.
+#[cfg(feature = "alloc")]
+#[derive(Default, Hash, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub struct ConvexPolygonVec<N> {
+    points: Vec<Vec2<N>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<N> AsRef<[Vec2<N>]> for ConvexPolygonVec<N> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[Vec2<N>] {
+        self.points()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<N> ConvexPolygonVec<N> {
+    ///Creates a polygon from points that make a convex shape.
+    #[inline(always)]
+    pub fn new(points: Vec<Vec2<N>>) -> ConvexPolygonVec<N> {
+        ConvexPolygonVec { points }
+    }
+
+    #[inline(always)]
+    pub fn points(&self) -> &[Vec2<N>] {
+        &self.points
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn into_points(self) -> Vec<Vec2<N>> {
+        self.points
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<N: PartialOrd + Copy> ConvexPolygonVec<N> {
+    ///Returns the smallest rect that contains every point, or `None` if there are no points.
+    #[inline(always)]
+    pub fn bounding_rect(&self) -> Option<Rect<N>> {
+        Rect::from_points(self.points().iter().copied())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy> ConvexPolygonVec<N> {
+    #[inline(always)]
+    pub fn contains_point(&self, point: Vec2<N>) -> bool {
+        contains_point(self.points(), point)
+    }
+
+    ///Returns true if the polygons touch, using the separating axis theorem.
+    #[inline(always)]
+    pub fn intersects_polygon(&self, other: &impl AsRef<[Vec2<N>]>) -> bool {
+        intersects(self.points(), other.as_ref())
+    }
+
+    ///Returns true if the polygon touches the rect.
+    #[inline(always)]
+    pub fn intersects_rect(&self, rect: &Rect<N>) -> bool {
+        intersects(self.points(), &rect.get_corners())
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn area(&self) -> N {
+        area(self.points())
+    }
+
+    ///Returns the center of mass of the polygon, or `None` if it has no area.
+    #[inline(always)]
+    pub fn centroid(&self) -> Option<Vec2<N>> {
+        centroid(self.points())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<N: num_traits::Num + PartialOrd + Copy> ConvexPolygonVec<N> {
+    ///Returns the part of the polygon that is inside of the rect.
+    ///The result is empty if they don't touch.
+    pub fn clip_to_rect(&self, rect: &Rect<N>) -> ConvexPolygonVec<N> {
+        let mut points = Vec::new();
+        clip_to_rect(self.points(), rect, |p| points.push(p));
+        ConvexPolygonVec { points }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<N> core::iter::FromIterator<Vec2<N>> for ConvexPolygonVec<N> {
+    #[inline(always)]
+    fn from_iter<I: IntoIterator<Item = Vec2<N>>>(iter: I) -> Self {
+        ConvexPolygonVec::new(iter.into_iter().collect())
+    }
+}
+
+impl<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy> Ray<N> {
+    ///Checks if a ray intersects a convex polygon.
+    ///If the ray starts inside the polygon, the tval is zero.
+    pub fn cast_to_polygon(&self, polygon: &impl AsRef<[Vec2<N>]>) -> CastResult<N> {
+        let points = polygon.as_ref();
+        if contains_point(points, self.point) {
+            return CastResult::Hit(N::zero());
+        }
+
+        cast_to_edges(self, points)
+    }
+}
+
+///Returns the closest hit of the ray on any edge of the polygon.
+pub(crate) fn cast_to_edges<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy>(
+    ray: &Ray<N>,
+    points: &[Vec2<N>],
+) -> CastResult<N> {
+    edges(points).fold(CastResult::NoHit, |closest, (a, b)| {
+        match (closest, ray.cast_to_segment(&segment(a, b))) {
+            (CastResult::Hit(c), CastResult::Hit(t)) if t < c => CastResult::Hit(t),
+            (CastResult::NoHit, hit) => hit,
+            (closest, _) => closest,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convex_polygon() {
+        //A diamond, clockwise when the y axis points up.
+        let diamond: ConvexPolygon<i32, 8> =
+            ConvexPolygon::from_points([vec2(0, 10), vec2(10, 0), vec2(0, -10), vec2(-10, 0)])
+                .unwrap();
+        assert_eq!(diamond.len(), 4);
+        assert_eq!(diamond.bounding_rect(), Some(rect(-10, 10, -10, 10)));
+        assert_eq!(diamond.area(), 200);
+        assert_eq!(diamond.centroid(), Some(vec2(0, 0)));
+
+        assert!(diamond.contains_point(vec2(5, 5)));
+        assert!(!diamond.contains_point(vec2(6, 5)));
+
+        assert!(diamond.intersects_rect(&rect(5, 20, 5, 20)));
+        assert!(!diamond.intersects_rect(&rect(6, 20, 6, 20)));
+
+        let square: ConvexPolygon<i32, 8> =
+            ConvexPolygon::from_points(rect(6, 20, 6, 20).get_corners()).unwrap();
+        assert!(!diamond.intersects_polygon(&square));
+        let tri: ConvexPolygon<i32, 8> =
+            ConvexPolygon::from_points([vec2(4, 4), vec2(20, 4), vec2(20, 20)]).unwrap();
+        assert!(diamond.intersects_polygon(&tri));
+
+        //Clipping a corner off adds a point.
+        let clipped = diamond.clip_to_rect(&rect(-20, 5, -20, 20)).unwrap();
+        assert_eq!(
+            clipped.points(),
+            &[
+                vec2(-10, 0),
+                vec2(0, 10),
+                vec2(5, 5),
+                vec2(5, -5),
+                vec2(0, -10)
+            ]
+        );
+        assert_eq!(clipped.area(), 175);
+        assert!(diamond
+            .clip_to_rect(&rect(20, 30, 0, 5))
+            .unwrap()
+            .is_empty());
+
+        //A square rotated a little needs eight points when clipped to a smaller square.
+        let small: Option<ConvexPolygon<i32, 4>> =
+            ConvexPolygon::from_points([vec2(0, 10), vec2(10, 0), vec2(0, -10), vec2(-10, 0)]);
+        assert!(small.unwrap().clip_to_rect(&rect(-7, 7, -7, 7)).is_none());
+        assert_eq!(diamond.clip_to_rect(&rect(-7, 7, -7, 7)).unwrap().len(), 8);
+
+        assert_eq!(
+            ray(vec2(-20, 5), vec2(1, 0)).cast_to_polygon(&diamond),
+            CastResult::Hit(15)
+        );
+        assert_eq!(
+            ray(vec2(1, 1), vec2(1, 0)).cast_to_polygon(&diamond),
+            CastResult::Hit(0)
+        );
+        assert_eq!(
+            ray(vec2(-20, 11), vec2(1, 0)).cast_to_polygon(&diamond),
+            CastResult::NoHit
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_convex_polygon_vec() {
+        let poly: ConvexPolygonVec<f64> = [vec2(0.0, 0.0), vec2(4.0, 0.0), vec2(4.0, 3.0)]
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(poly.area(), 6.0);
+        assert_eq!(poly.centroid(), Some(vec2(8.0 / 3.0, 1.0)));
+
+        let clipped = poly.clip_to_rect(&rect(2.0, 10.0, -1.0, 10.0));
+        assert_eq!(
+            clipped.points(),
+            &[
+                vec2(2.0, 1.5),
+                vec2(2.0, 0.0),
+                vec2(4.0, 0.0),
+                vec2(4.0, 3.0)
+            ]
+        );
+        assert_eq!(clipped.area(), 4.5);
+
+        //Touching at a single edge.
+        assert!(poly.intersects_rect(&rect(4.0, 5.0, 0.0, 1.0)));
+        assert!(!poly.intersects_rect(&rect(4.5, 5.0, 0.0, 1.0)));
+        assert_eq!(
+            ray(vec2(2.0, 5.0), vec2(0.0, -1.0)).cast_to_polygon(&poly),
+            CastResult::Hit(3.5)
+        );
+    }
+}
@@ -0,0 +1,641 @@
+//! Exact orientation and incircle tests.
+//!
+//! The float versions first compute the result normally, and only recompute it exactly when
+//! the rounding error could have changed the sign. They are based on Jonathan Shewchuk's
+//! "Adaptive Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates",
+//! and assume that no intermediate value overflows or underflows.
+//!
+//! The exact incircle test works in stages like Shewchuk's `incircleadapt` and `incircleexact`.
+//! Its largest buffers hold 384 floats, so it uses a few kilobytes of stack in the rare cases it is needed.
+//!
+//! The integer versions compute the result with wider integers, so they are always exact.
+
+use crate::*;
+use core::cmp::Ordering;
+use core::ops::*;
+use num_traits::float::FloatCore;
+
+///Numbers with exact orientation and incircle tests.
+///Implemented for `f32`, `f64`, `i32` and `i64`.
+pub trait Predicates: Copy {
+    ///See `orient2d`.
+    fn orient2d(a: Vec2<Self>, b: Vec2<Self>, c: Vec2<Self>) -> Ordering;
+    ///See `incircle`.
+    fn incircle(a: Vec2<Self>, b: Vec2<Self>, c: Vec2<Self>, d: Vec2<Self>) -> Ordering;
+}
+
+///Returns `Greater` if the points are counter clockwise when the y axis points up,
+///`Less` if they are clockwise, and `Equal` if they are on one line.
+#[inline(always)]
+pub fn orient2d<N: Predicates>(a: Vec2<N>, b: Vec2<N>, c: Vec2<N>) -> Ordering {
+    N::orient2d(a, b, c)
+}
+
+///If a, b and c are counter clockwise when the y axis points up, returns `Greater` if d is inside
+///the circle through them, `Less` if d is outside of it, and `Equal` if d is on it.
+///The result is reversed if a, b and c are clockwise.
+#[inline(always)]
+pub fn incircle<N: Predicates>(a: Vec2<N>, b: Vec2<N>, c: Vec2<N>, d: Vec2<N>) -> Ordering {
+    N::incircle(a, b, c, d)
+}
+
+///Floats whose products can be computed exactly by splitting them in half.
+trait SplitFloat: FloatCore {
+    ///`2^ceil(p/2) + 1` where p is the number of bits in the mantissa.
+    const SPLITTER: Self;
+    ///Half the distance from one to the next float.
+    const EPSILON: Self;
+}
+
+impl SplitFloat for f32 {
+    const SPLITTER: f32 = 4097.0;
+    const EPSILON: f32 = f32::EPSILON / 2.0;
+}
+
+impl SplitFloat for f64 {
+    const SPLITTER: f64 = 134_217_729.0;
+    const EPSILON: f64 = f64::EPSILON / 2.0;
+}
+
+#[inline(always)]
+fn num<N: SplitFloat>(a: u8) -> N {
+    N::from(a).unwrap()
+}
+
+///Returns the rounded sum and its rounding error.
+#[inline(always)]
+fn two_sum<N: SplitFloat>(a: N, b: N) -> (N, N) {
+    let x = a + b;
+    let bvirt = x - a;
+    let avirt = x - bvirt;
+    (x, (a - avirt) + (b - bvirt))
+}
+
+///Like `two_sum`, but only correct if `|a| >= |b|`.
+#[inline(always)]
+fn fast_two_sum<N: SplitFloat>(a: N, b: N) -> (N, N) {
+    let x = a + b;
+    (x, b - (x - a))
+}
+
+///Returns the rounded difference and its rounding error.
+#[inline(always)]
+fn two_diff<N: SplitFloat>(a: N, b: N) -> (N, N) {
+    let x = a - b;
+    let bvirt = a - x;
+    let avirt = x + bvirt;
+    (x, (a - avirt) + (bvirt - b))
+}
+
+///Splits a float into two halves that each have half of the bits of the mantissa.
+#[inline(always)]
+fn split<N: SplitFloat>(a: N) -> (N, N) {
+    let c = N::SPLITTER * a;
+    let hi = c - (c - a);
+    (hi, a - hi)
+}
+
+///Returns the rounded product and its rounding error.
+#[inline(always)]
+fn two_product<N: SplitFloat>(a: N, b: N) -> (N, N) {
+    let x = a * b;
+    let (ahi, alo) = split(a);
+    let (bhi, blo) = split(b);
+    let err = ((x - ahi * bhi) - alo * bhi) - ahi * blo;
+    (x, alo * blo - err)
+}
+
+///A number stored exactly as a sum of floats that don't overlap,
+///ordered from the smallest magnitude to the largest.
+///There is always at least one term, and the only term that can be zero is the only term.
+#[derive(Copy, Clone)]
+struct Expansion<N, const M: usize> {
+    terms: [N; M],
+    len: usize,
+}
+
+impl<N: SplitFloat, const M: usize> Expansion<N, M> {
+    #[inline(always)]
+    fn new() -> Self {
+        Expansion {
+            terms: [N::zero(); M],
+            len: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn terms(&self) -> &[N] {
+        &self.terms[..self.len]
+    }
+
+    #[inline(always)]
+    fn push(&mut self, a: N) {
+        self.terms[self.len] = a;
+        self.len += 1;
+    }
+
+    #[inline(always)]
+    fn push_nonzero(&mut self, a: N) {
+        if a != N::zero() {
+            self.push(a);
+        }
+    }
+
+    ///Pushes the last term, unless it is zero and there are already other terms.
+    #[inline(always)]
+    fn finish(mut self, a: N) -> Self {
+        if a != N::zero() || self.len == 0 {
+            self.push(a);
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn neg(mut self) -> Self {
+        for a in self.terms[..self.len].iter_mut() {
+            *a = -*a;
+        }
+        self
+    }
+
+    ///The largest term has the sign of the whole sum.
+    #[inline(always)]
+    fn sign(&self) -> Ordering {
+        self.terms[self.len - 1]
+            .partial_cmp(&N::zero())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+///Returns `a - b` exactly.
+#[inline(always)]
+fn diff<N: SplitFloat>(a: N, b: N) -> Expansion<N, 2> {
+    let (x, y) = two_diff(a, b);
+    let mut e = Expansion::new();
+    e.push_nonzero(y);
+    e.finish(x)
+}
+
+///Returns `a * b` exactly.
+#[inline(always)]
+fn prod<N: SplitFloat>(a: N, b: N) -> Expansion<N, 2> {
+    let (x, y) = two_product(a, b);
+    let mut e = Expansion::new();
+    e.push_nonzero(y);
+    e.finish(x)
+}
+
+///Returns the sum of two expansions exactly.
+fn sum<N: SplitFloat, const M: usize>(e: &[N], f: &[N]) -> Expansion<N, M> {
+    let (mut i, mut j) = (0, 0);
+
+    //Merge the terms of both expansions from the smallest magnitude to the largest.
+    let mut next = || {
+        let take_e = match (e.get(i), f.get(j)) {
+            (Some(&a), Some(&b)) => (b > a) == (b > -a),
+            (Some(_), None) => true,
+            _ => false,
+        };
+        if take_e {
+            i += 1;
+            e[i - 1]
+        } else {
+            j += 1;
+            f[j - 1]
+        }
+    };
+
+    let mut h = Expansion::new();
+    let mut q = next();
+    for _ in 1..e.len() + f.len() {
+        let (x, y) = two_sum(q, next());
+        h.push_nonzero(y);
+        q = x;
+    }
+    h.finish(q)
+}
+
+///Returns the expansion multiplied by a float exactly.
+fn scale<N: SplitFloat, const M: usize>(e: &[N], b: N) -> Expansion<N, M> {
+    let mut h = Expansion::new();
+    let (mut q, y) = two_product(e[0], b);
+    h.push_nonzero(y);
+    for &a in &e[1..] {
+        let (p1, p0) = two_product(a, b);
+        let (s, y) = two_sum(q, p0);
+        h.push_nonzero(y);
+        let (x, y) = fast_two_sum(p1, s);
+        h.push_nonzero(y);
+        q = x;
+    }
+    h.finish(q)
+}
+
+///Returns the product of two expansions exactly.
+///`M` must be at least twice the product of their lengths.
+fn product<N: SplitFloat, const M: usize>(e: &[N], f: &[N]) -> Expansion<N, M> {
+    let mut acc: Expansion<N, M> = scale(e, f[0]);
+    for &b in &f[1..] {
+        let s: Expansion<N, M> = scale(e, b);
+        acc = sum(acc.terms(), s.terms());
+    }
+    acc
+}
+
+///Returns `a.x * b.y - b.x * a.y` exactly.
+#[inline(always)]
+fn cross_point<N: SplitFloat>(a: Vec2<N>, b: Vec2<N>) -> Expansion<N, 4> {
+    sum(prod(a.x, b.y).terms(), prod(b.x, a.y).neg().terms())
+}
+
+///Returns `(p.x * p.x + p.y * p.y) * e` exactly.
+///`e` can have at most 12 terms, and `M` must be at least 8 times its length.
+fn lifted<N: SplitFloat, const M: usize>(p: Vec2<N>, e: &[N]) -> Expansion<N, M> {
+    let x: Expansion<N, 24> = scale(e, p.x);
+    let xx: Expansion<N, 48> = scale(x.terms(), p.x);
+    let y: Expansion<N, 24> = scale(e, p.y);
+    let yy: Expansion<N, 48> = scale(y.terms(), p.y);
+    sum(xx.terms(), yy.terms())
+}
+
+///Returns `x1 * y2 - y1 * x2` exactly.
+fn cross<N: SplitFloat>(
+    x1: &Expansion<N, 2>,
+    y1: &Expansion<N, 2>,
+    x2: &Expansion<N, 2>,
+    y2: &Expansion<N, 2>,
+) -> Expansion<N, 16> {
+    let a: Expansion<N, 8> = product(x1.terms(), y2.terms());
+    let b: Expansion<N, 8> = product(y1.terms(), x2.terms());
+    sum(a.terms(), b.neg().terms())
+}
+
+fn orient2d_float<N: SplitFloat>(a: Vec2<N>, b: Vec2<N>, c: Vec2<N>) -> Ordering {
+    let left = (a.x - c.x) * (b.y - c.y);
+    let right = (a.y - c.y) * (b.x - c.x);
+    let det = left - right;
+
+    let bound = (num::<N>(3) + num::<N>(16) * N::EPSILON) * N::EPSILON * (left.abs() + right.abs());
+    if det.abs() > bound {
+        return det.partial_cmp(&N::zero()).unwrap_or(Ordering::Equal);
+    }
+
+    let det = cross(
+        &diff(a.x, c.x),
+        &diff(a.y, c.y),
+        &diff(b.x, c.x),
+        &diff(b.y, c.y),
+    );
+    det.sign()
+}
+
+fn incircle_float<N: SplitFloat>(a: Vec2<N>, b: Vec2<N>, c: Vec2<N>, d: Vec2<N>) -> Ordering {
+    let (ad, bd, cd) = (a - d, b - d, c - d);
+    let (bc1, bc2) = (bd.x * cd.y, cd.x * bd.y);
+    let (ca1, ca2) = (cd.x * ad.y, ad.x * cd.y);
+    let (ab1, ab2) = (ad.x * bd.y, bd.x * ad.y);
+    let alift = ad.x * ad.x + ad.y * ad.y;
+    let blift = bd.x * bd.x + bd.y * bd.y;
+    let clift = cd.x * cd.x + cd.y * cd.y;
+
+    let det = alift * (bc1 - bc2) + blift * (ca1 - ca2) + clift * (ab1 - ab2);
+    let permanent = (bc1.abs() + bc2.abs()) * alift
+        + (ca1.abs() + ca2.abs()) * blift
+        + (ab1.abs() + ab2.abs()) * clift;
+
+    let bound = (num::<N>(10) + num::<N>(96) * N::EPSILON) * N::EPSILON * permanent;
+    if det.abs() > bound {
+        return det.partial_cmp(&N::zero()).unwrap_or(Ordering::Equal);
+    }
+
+    //If the differences were computed without rounding, the determinant can be computed exactly from them.
+    //Each term has at most 32 floats.
+    let exact = |p: Vec2<N>| two_diff(p.x, d.x).1 == N::zero() && two_diff(p.y, d.y).1 == N::zero();
+    if exact(a) && exact(b) && exact(c) {
+        let aterm: Expansion<N, 32> = lifted(ad, cross_point(bd, cd).terms());
+        let bterm: Expansion<N, 32> = lifted(bd, cross_point(cd, ad).terms());
+        let cterm: Expansion<N, 32> = lifted(cd, cross_point(ad, bd).terms());
+        let ab: Expansion<N, 64> = sum(aterm.terms(), bterm.terms());
+        let det: Expansion<N, 96> = sum(ab.terms(), cterm.terms());
+        return det.sign();
+    }
+
+    incircle_exact(a, b, c, d)
+}
+
+///Computes the incircle determinant exactly from the points themselves instead of their differences.
+///This is Shewchuk's `incircleexact`, and it needs at most 384 floats for the result.
+fn incircle_exact<N: SplitFloat>(a: Vec2<N>, b: Vec2<N>, c: Vec2<N>, d: Vec2<N>) -> Ordering {
+    let sum3 = |x: &[N], y: &[N], z: &[N]| -> Expansion<N, 12> {
+        let t: Expansion<N, 8> = sum(x, y);
+        sum(t.terms(), z)
+    };
+
+    let (ab, bc, cd) = (cross_point(a, b), cross_point(b, c), cross_point(c, d));
+    let (da, ac, bd) = (cross_point(d, a), cross_point(a, c), cross_point(b, d));
+    let bcd = sum3(bc.terms(), cd.terms(), bd.neg().terms());
+    let cda = sum3(cd.terms(), da.terms(), ac.terms());
+    let dab = sum3(da.terms(), ab.terms(), bd.terms());
+    let abc = sum3(ab.terms(), bc.terms(), ac.neg().terms());
+
+    let adet: Expansion<N, 96> = lifted(a, bcd.terms());
+    let bdet: Expansion<N, 96> = lifted(b, cda.terms());
+    let cdet: Expansion<N, 96> = lifted(c, dab.terms());
+    let ddet: Expansion<N, 96> = lifted(d, abc.terms());
+
+    let ab: Expansion<N, 192> = sum(adet.terms(), bdet.neg().terms());
+    let cd: Expansion<N, 192> = sum(cdet.terms(), ddet.neg().terms());
+    let det: Expansion<N, 384> = sum(ab.terms(), cd.terms());
+    det.sign()
+}
+
+impl Predicates for f32 {
+    #[inline(always)]
+    fn orient2d(a: Vec2<f32>, b: Vec2<f32>, c: Vec2<f32>) -> Ordering {
+        orient2d_float(a, b, c)
+    }
+
+    #[inline(always)]
+    fn incircle(a: Vec2<f32>, b: Vec2<f32>, c: Vec2<f32>, d: Vec2<f32>) -> Ordering {
+        incircle_float(a, b, c, d)
+    }
+}
+
+impl Predicates for f64 {
+    #[inline(always)]
+    fn orient2d(a: Vec2<f64>, b: Vec2<f64>, c: Vec2<f64>) -> Ordering {
+        orient2d_float(a, b, c)
+    }
+
+    #[inline(always)]
+    fn incircle(a: Vec2<f64>, b: Vec2<f64>, c: Vec2<f64>, d: Vec2<f64>) -> Ordering {
+        incircle_float(a, b, c, d)
+    }
+}
+
+///A 320 bit two's complement integer, which is enough for the integer incircle test.
+///The words are stored from least to most significant.
+#[derive(Copy, Clone)]
+struct Wide([u64; 5]);
+
+impl From<i128> for Wide {
+    #[inline(always)]
+    fn from(a: i128) -> Wide {
+        let ext = if a < 0 { u64::MAX } else { 0 };
+        Wide([a as u64, (a >> 64) as u64, ext, ext, ext])
+    }
+}
+
+impl Wide {
+    #[inline(always)]
+    fn is_negative(&self) -> bool {
+        self.0[4] >> 63 == 1
+    }
+
+    #[inline(always)]
+    fn abs(self) -> Wide {
+        if self.is_negative() {
+            -self
+        } else {
+            self
+        }
+    }
+
+    #[inline(always)]
+    fn sign(&self) -> Ordering {
+        if self.is_negative() {
+            Ordering::Less
+        } else if self.0.iter().all(|&a| a == 0) {
+            Ordering::Equal
+        } else {
+            Ordering::Greater
+        }
+    }
+}
+
+impl Add for Wide {
+    type Output = Wide;
+    fn add(self, rhs: Wide) -> Wide {
+        let mut out = [0; 5];
+        let mut carry = false;
+        for (o, (a, b)) in out.iter_mut().zip(self.0.iter().zip(rhs.0.iter())) {
+            let (x, c1) = a.overflowing_add(*b);
+            let (x, c2) = x.overflowing_add(carry as u64);
+            *o = x;
+            carry = c1 || c2;
+        }
+        Wide(out)
+    }
+}
+
+impl Neg for Wide {
+    type Output = Wide;
+    #[inline(always)]
+    fn neg(self) -> Wide {
+        let mut out = self.0;
+        for a in out.iter_mut() {
+            *a = !*a;
+        }
+        Wide(out) + Wide::from(1)
+    }
+}
+
+impl Sub for Wide {
+    type Output = Wide;
+    #[inline(always)]
+    fn sub(self, rhs: Wide) -> Wide {
+        self + -rhs
+    }
+}
+
+impl Mul for Wide {
+    type Output = Wide;
+    fn mul(self, rhs: Wide) -> Wide {
+        let negative = self.is_negative() != rhs.is_negative();
+        let (a, b) = (self.abs().0, rhs.abs().0);
+
+        //Long multiplication, dropping words past the end. The results are small enough that they are always zero.
+        let mut out = [0u64; 5];
+        for i in 0..5 {
+            let mut carry = 0u128;
+            for j in 0..5 - i {
+                let t = a[i] as u128 * b[j] as u128 + out[i + j] as u128 + carry;
+                out[i + j] = t as u64;
+                carry = t >> 64;
+            }
+        }
+
+        if negative {
+            -Wide(out)
+        } else {
+            Wide(out)
+        }
+    }
+}
+
+///Differences of 64 bit integers fit in 65 bits, so they are computed in `i128` and then widened.
+fn orient2d_wide(a: Vec2<i128>, b: Vec2<i128>, c: Vec2<i128>) -> Ordering {
+    let ab = (b - a).inner_into::<Wide>();
+    let ac = (c - a).inner_into::<Wide>();
+    ab.cross(ac).sign()
+}
+
+fn incircle_wide(a: Vec2<i128>, b: Vec2<i128>, c: Vec2<i128>, d: Vec2<i128>) -> Ordering {
+    let ad = (a - d).inner_into::<Wide>();
+    let bd = (b - d).inner_into::<Wide>();
+    let cd = (c - d).inner_into::<Wide>();
+    let lift = |v: Vec2<Wide>| v.x * v.x + v.y * v.y;
+
+    let det = lift(ad) * bd.cross(cd) + lift(bd) * cd.cross(ad) + lift(cd) * ad.cross(bd);
+    det.sign()
+}
+
+impl Predicates for i32 {
+    #[inline(always)]
+    fn orient2d(a: Vec2<i32>, b: Vec2<i32>, c: Vec2<i32>) -> Ordering {
+        //The products have at most 66 bits.
+        let (a, b, c) = (
+            a.inner_into::<i128>(),
+            b.inner_into::<i128>(),
+            c.inner_into::<i128>(),
+        );
+        (b - a).cross(c - a).cmp(&0)
+    }
+
+    #[inline(always)]
+    fn incircle(a: Vec2<i32>, b: Vec2<i32>, c: Vec2<i32>, d: Vec2<i32>) -> Ordering {
+        incircle_wide(
+            a.inner_into(),
+            b.inner_into(),
+            c.inner_into(),
+            d.inner_into(),
+        )
+    }
+}
+
+impl Predicates for i64 {
+    #[inline(always)]
+    fn orient2d(a: Vec2<i64>, b: Vec2<i64>, c: Vec2<i64>) -> Ordering {
+        orient2d_wide(a.inner_into(), b.inner_into(), c.inner_into())
+    }
+
+    #[inline(always)]
+    fn incircle(a: Vec2<i64>, b: Vec2<i64>, c: Vec2<i64>, d: Vec2<i64>) -> Ordering {
+        incircle_wide(
+            a.inner_into(),
+            b.inner_into(),
+            c.inner_into(),
+            d.inner_into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orient2d_float() {
+        //The points are on the line y=x, except that the first point is moved by a few ulps.
+        //The exact orientation is the sign of `py - px`.
+        let ulp = f64::EPSILON / 2.0;
+        for i in 0..16 {
+            for j in 0..16 {
+                let p = vec2(0.5 + i as f64 * ulp, 0.5 + j as f64 * ulp);
+                let expected = j.cmp(&i);
+                assert_eq!(orient2d(p, vec2(12.0, 12.0), vec2(24.0, 24.0)), expected);
+                assert_eq!(orient2d(vec2(12.0, 12.0), vec2(24.0, 24.0), p), expected);
+
+                let ulp = f32::EPSILON / 2.0;
+                let p = vec2(0.5 + i as f32 * ulp, 0.5 + j as f32 * ulp);
+                assert_eq!(orient2d(p, vec2(12.0, 12.0), vec2(24.0, 24.0)), expected);
+            }
+        }
+
+        assert_eq!(
+            orient2d(vec2(0.0, 0.0), vec2(1.0, 0.0), vec2(0.0, 1.0)),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn test_incircle_float() {
+        let (a, b, c) = (vec2(1.0, 0.0), vec2(0.0, 1.0), vec2(-1.0, 0.0));
+        assert_eq!(incircle(a, b, c, vec2(0.0, 0.0)), Ordering::Greater);
+        assert_eq!(incircle(a, b, c, vec2(2.0, 0.0)), Ordering::Less);
+        assert_eq!(incircle(a, b, c, vec2(0.0, -1.0)), Ordering::Equal);
+        assert_eq!(incircle(a, c, b, vec2(0.0, 0.0)), Ordering::Less);
+
+        //Moved off of the circle by one ulp.
+        let ulp = f64::EPSILON / 2.0;
+        assert_eq!(incircle(a, b, c, vec2(0.0, -1.0 + ulp)), Ordering::Greater);
+        assert_eq!(
+            incircle(a, b, c, vec2(0.0, -1.0 - 2.0 * ulp)),
+            Ordering::Less
+        );
+        assert_eq!(incircle(a, b, c, vec2(ulp, -1.0)), Ordering::Less);
+
+        let a = vec2(1.0f32, 0.0);
+        let (b, c) = (vec2(0.0f32, 1.0), vec2(-1.0f32, 0.0));
+        let ulp = f32::EPSILON / 2.0;
+        assert_eq!(incircle(a, b, c, vec2(0.0, -1.0 + ulp)), Ordering::Greater);
+        assert_eq!(incircle(a, b, c, vec2(0.0, -1.0)), Ordering::Equal);
+
+        //The differences from d are rounded, so these are computed from the points themselves.
+        let r = (1u64 << 60) as f64;
+        let (a, b, c) = (vec2(r, 0.0), vec2(0.0, r), vec2(-r, 0.0));
+        let tiny = 1.0 / 1024.0;
+        assert_eq!(incircle(a, b, c, vec2(tiny, -r)), Ordering::Less);
+        assert_eq!(incircle(a, b, c, vec2(tiny, -r + 128.0)), Ordering::Greater);
+        assert_eq!(incircle(a, c, b, vec2(tiny, -r)), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_incircle_exact() {
+        let points = [(3, 0), (0, 3), (-3, 0), (0, -3), (1, 1), (5, -2), (-7, 4)];
+        for &a in &points {
+            for &b in &points {
+                for &c in &points {
+                    for &d in &points {
+                        let [a, b, c, d] = [a, b, c, d].map(|(x, y)| vec2(x, y));
+                        let f = |p: Vec2<i64>| p.inner_as::<f64>() / 8.0;
+                        assert_eq!(incircle_exact(f(a), f(b), f(c), f(d)), incircle(a, b, c, d));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_predicates_int() {
+        let (min, max) = (i64::MIN, i64::MAX);
+        assert_eq!(
+            orient2d(vec2(min, min), vec2(max, max), vec2(-1, -1)),
+            Ordering::Equal
+        );
+        assert_eq!(
+            orient2d(vec2(min, min), vec2(max, max), vec2(0, 1)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            orient2d(vec2(min, max), vec2(max, min), vec2(max, max)),
+            Ordering::Greater
+        );
+
+        let (a, b, c) = (vec2(max, 0), vec2(0, max), vec2(-max, 0));
+        assert_eq!(incircle(a, b, c, vec2(0, -max)), Ordering::Equal);
+        assert_eq!(incircle(a, b, c, vec2(0, -max + 1)), Ordering::Greater);
+        assert_eq!(incircle(a, b, c, vec2(1, -max)), Ordering::Less);
+        assert_eq!(incircle(a, b, c, vec2(min, min)), Ordering::Less);
+
+        let (min, max) = (i32::MIN, i32::MAX);
+        assert_eq!(
+            orient2d(vec2(min, min), vec2(max, max), vec2(0, 1)),
+            Ordering::Greater
+        );
+        let (a, b, c) = (vec2(max, 0), vec2(0, max), vec2(-max, 0));
+        assert_eq!(incircle(a, b, c, vec2(0, -max)), Ordering::Equal);
+        assert_eq!(incircle(a, b, c, vec2(0, -max + 1)), Ordering::Greater);
+        assert_eq!(incircle(a, b, c, vec2(1, -max)), Ordering::Less);
+    }
+}
@@ -0,0 +1,272 @@
+use crate::*;
+use alloc::vec::Vec;
+
+struct Node<N> {
+    rect: Rect<N>,
+    depth: usize,
+    //Keys of the items that belong to this node.
+    items: Vec<usize>,
+    //Index of the first of four children, which are stored next to each other.
+    children: Option<usize>,
+}
+
+///A quadtree of items with bounding rects.
+///
+///Each node splits its rect into four children at its center once it has more than `bucket_size` items,
+///unless it is already at `max_depth`. Items are stored in the deepest node that completely contains them,
+///so items that cross a divider stay at the node that split them.
+///Items that are not inside of the rect of the tree are stored in the root.
+#[must_use]
+pub struct QuadTree<T: HasRect> {
+    nodes: Vec<Node<T::Num>>,
+    items: Vec<Option<T>>,
+    free: Vec<usize>,
+    max_depth: usize,
+    bucket_size: usize,
+}
+
+impl<T: HasRect> QuadTree<T> {
+    ///The rects of every node, for debug drawing.
+    #[inline(always)]
+    pub fn node_rects(&self) -> impl Iterator<Item = &Rect<T::Num>> {
+        self.nodes.iter().map(|a| &a.rect)
+    }
+
+    #[inline(always)]
+    pub fn get(&self, key: usize) -> Option<&T> {
+        self.items.get(key)?.as_ref()
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len() - self.free.len()
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline(always)]
+    fn item(&self, key: usize) -> &T {
+        self.items[key].as_ref().unwrap()
+    }
+
+    ///Calls the function on every item that passes `item_test`,
+    ///only visiting the children whose rect passes `node_test`.
+    fn query(
+        &self,
+        index: usize,
+        node_test: &impl Fn(&Rect<T::Num>) -> bool,
+        item_test: &impl Fn(&Rect<T::Num>) -> bool,
+        func: &mut impl FnMut(usize, &T),
+    ) {
+        let node = &self.nodes[index];
+        for &key in node.items.iter() {
+            let item = self.item(key);
+            if item_test(item.get_rect()) {
+                func(key, item);
+            }
+        }
+        if let Some(first) = node.children {
+            for child in first..first + 4 {
+                if node_test(&self.nodes[child].rect) {
+                    self.query(child, node_test, item_test, func);
+                }
+            }
+        }
+    }
+}
+
+impl<T: HasRect> QuadTree<T>
+where
+    T::Num: num_traits::Num + PartialOrd + Copy,
+{
+    ///Creates an empty tree covering the specified rect.
+    pub fn new(rect: Rect<T::Num>, max_depth: usize, bucket_size: usize) -> QuadTree<T> {
+        QuadTree {
+            nodes: alloc::vec![Node {
+                rect,
+                depth: 0,
+                items: Vec::new(),
+                children: None,
+            }],
+            items: Vec::new(),
+            free: Vec::new(),
+            max_depth,
+            bucket_size,
+        }
+    }
+
+    ///Returns the child of the node that completely contains the rect, if there is one.
+    fn child_for(&self, index: usize, rect: &Rect<T::Num>) -> Option<usize> {
+        let first = self.nodes[index].children?;
+        (first..first + 4).find(|&c| self.nodes[c].rect.contains_rect(rect))
+    }
+
+    ///Returns the node that an item with the specified rect belongs in.
+    fn find_node(&self, rect: &Rect<T::Num>) -> usize {
+        let mut index = 0;
+        while let Some(child) = self.child_for(index, rect) {
+            index = child;
+        }
+        index
+    }
+
+    fn split(&mut self, index: usize) {
+        let node = &self.nodes[index];
+        let rect = node.rect;
+        let center = rect.derive_center();
+
+        //Rects that are too small to have a center strictly inside them can't be split.
+        let inside = |range: &Range<T::Num>, c: T::Num| range.start < c && c < range.end;
+        if !inside(&rect.x, center.x) || !inside(&rect.y, center.y) {
+            return;
+        }
+
+        let depth = node.depth + 1;
+        let (left, right) = rect.subdivide(XAXIS, center.x);
+        let (a, b) = left.subdivide(YAXIS, center.y);
+        let (c, d) = right.subdivide(YAXIS, center.y);
+
+        let first = self.nodes.len();
+        for rect in [a, b, c, d] {
+            self.nodes.push(Node {
+                rect,
+                depth,
+                items: Vec::new(),
+                children: None,
+            });
+        }
+        self.nodes[index].children = Some(first);
+
+        let items = core::mem::take(&mut self.nodes[index].items);
+        for key in items {
+            let target = self
+                .child_for(index, self.item(key).get_rect())
+                .unwrap_or(index);
+            self.nodes[target].items.push(key);
+        }
+
+        for child in first..first + 4 {
+            self.split_if_full(child);
+        }
+    }
+
+    fn split_if_full(&mut self, index: usize) {
+        let node = &self.nodes[index];
+        if node.children.is_none()
+            && node.items.len() > self.bucket_size
+            && node.depth < self.max_depth
+        {
+            self.split(index);
+        }
+    }
+
+    ///Inserts an item into the tree.
+    ///Returns a key that can be used to remove the item.
+    pub fn insert(&mut self, item: T) -> usize {
+        let index = self.find_node(item.get_rect());
+
+        let key = match self.free.pop() {
+            Some(key) => {
+                self.items[key] = Some(item);
+                key
+            }
+            None => {
+                self.items.push(Some(item));
+                self.items.len() - 1
+            }
+        };
+
+        self.nodes[index].items.push(key);
+        self.split_if_full(index);
+        key
+    }
+
+    ///Removes the item with the specified key.
+    pub fn remove(&mut self, key: usize) -> Option<T> {
+        let index = self.find_node(self.get(key)?.get_rect());
+        self.nodes[index].items.retain(|&k| k != key);
+        self.free.push(key);
+        self.items[key].take()
+    }
+
+    ///Calls the function on every item whose rect intersects the specified rect.
+    pub fn query_rect(&self, rect: &Rect<T::Num>, mut func: impl FnMut(usize, &T)) {
+        let test = |r: &Rect<T::Num>| r.intersects_rect(rect);
+        self.query(0, &test, &test, &mut func);
+    }
+
+    ///Calls the function on every item whose rect contains the point.
+    pub fn query_point(&self, point: Vec2<T::Num>, mut func: impl FnMut(usize, &T)) {
+        let test = |r: &Rect<T::Num>| r.contains_point(point);
+        self.query(0, &test, &test, &mut func);
+    }
+
+    ///Calls the function on every item whose rect intersects the circle.
+    pub fn query_circle(&self, circle: &Circle<T::Num>, mut func: impl FnMut(usize, &T)) {
+        let test = |r: &Rect<T::Num>| circle.intersects_rect(r);
+        self.query(0, &test, &test, &mut func);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_quadtree() {
+        let mut tree = QuadTree::new(rect(0, 100, 0, 100), 4, 2);
+        let keys: Vec<_> = (0..10)
+            .map(|i| tree.insert(rect(i * 10, i * 10 + 5, i * 10, i * 10 + 5)))
+            .collect();
+        let straddle = tree.insert(rect(45, 55, 0, 5));
+        assert_eq!(tree.len(), 11);
+        assert!(tree.node_rects().count() > 1);
+        assert!(tree.node_rects().any(|r| *r == rect(0, 50, 0, 50)));
+
+        let mut found = vec![];
+        tree.query_rect(&rect(0, 22, 0, 22), |k, _| found.push(k));
+        found.sort_unstable();
+        assert_eq!(found, vec![keys[0], keys[1], keys[2]]);
+
+        let mut found = vec![];
+        tree.query_point(vec2(50, 3), |k, _| found.push(k));
+        assert_eq!(found, vec![straddle]);
+
+        let mut found = vec![];
+        tree.query_circle(&circle(vec2(33, 33), 3), |k, _| found.push(k));
+        assert_eq!(found, vec![keys[3]]);
+
+        assert_eq!(tree.remove(keys[3]), Some(rect(30, 35, 30, 35)));
+        assert_eq!(tree.remove(keys[3]), None);
+        let mut found = vec![];
+        tree.query_circle(&circle(vec2(33, 33), 3), |k, _| found.push(k));
+        assert!(found.is_empty());
+        assert_eq!(tree.len(), 10);
+    }
+
+    #[test]
+    fn test_quadtree_float() {
+        let mut tree = QuadTree::new(rect(0.0f32, 1.0, 0.0, 1.0), 8, 1);
+        for i in 0..20 {
+            let a = i as f32 / 20.0;
+            tree.insert((rect(a, a + 0.01, 1.0 - a - 0.01, 1.0 - a), i));
+        }
+        //Outside of the tree's rect.
+        tree.insert((rect(2.0, 3.0, 2.0, 3.0), 20));
+
+        let mut found = vec![];
+        tree.query_rect(&rect(0.0, 0.5, 0.5, 1.0), |_, a| found.push(a.1));
+        found.sort_unstable();
+        assert_eq!(found, (0..11).collect::<Vec<_>>());
+
+        let mut found = vec![];
+        tree.query_point(vec2(2.5, 2.5), |_, a| found.push(a.1));
+        assert_eq!(found, vec![20]);
+    }
+}
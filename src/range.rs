@@ -1,9 +1,50 @@
 use core::convert::TryInto;
+use core::marker::PhantomData;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 ///Convenience function to create a Range.
 #[inline(always)]
 pub fn range<T>(start: T, end: T) -> Range<T> {
-    Range { start, end }
+    Range::new(start, end)
+}
+
+///Describes whether the start and end values of a `Range` are inside of it.
+///Implemented by `Closed`, `HalfOpen` and `Open`.
+pub trait Bound:
+    Copy + Clone + core::fmt::Debug + Default + Eq + core::hash::Hash + Send + Sync
+{
+    ///True if the start value is inside of the range.
+    const INCLUDES_START: bool;
+    ///True if the end value is inside of the range.
+    const INCLUDES_END: bool;
+}
+
+///The range `[start,end]`. This is the default bound.
+#[derive(Default, Hash, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Closed;
+impl Bound for Closed {
+    const INCLUDES_START: bool = true;
+    const INCLUDES_END: bool = true;
+}
+
+///The range `[start,end)`.
+#[derive(Default, Hash, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HalfOpen;
+impl Bound for HalfOpen {
+    const INCLUDES_START: bool = true;
+    const INCLUDES_END: bool = false;
+}
+
+///The range `(start,end)`.
+#[derive(Default, Hash, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Open;
+impl Bound for Open {
+    const INCLUDES_START: bool = false;
+    const INCLUDES_END: bool = false;
 }
 
 ///A 1D range. Internally represented as start and end. (as opposed to a start and length)
@@ -11,19 +52,51 @@ pub fn range<T>(start: T, end: T) -> Range<T> {
 ///There is no protection against "degenerate" Ranges where start>end.
 ///
 ///
-///A point is consindered inside of a range if the point is in `[start,end]`.
+///Whether the start and end are inside of the range is decided by the `Bound` type parameter.
+///By default a point is consindered inside of a range if the point is in `[start,end]`.
+///Use `into_bound` to get a `HalfOpen` or `Open` range.
+///Open bounds are treated as intervals of real numbers, so `(0,1)` intersects `(0,2)` even for integers.
 ///
 #[derive(Default,Hash,Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[must_use]
-pub struct Range<T> {
+pub struct Range<T, B = Closed> {
     pub start: T,
     pub end: T,
+    ///Only marks the `Bound`. Create ranges with `Range::new` and `into_bound` instead of a struct literal.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub bound: PhantomData<B>,
 }
 
 impl<T> Range<T> {
     #[inline(always)]
     pub fn new(start: T, end: T) -> Range<T> {
-        Range { start, end }
+        Range {
+            start,
+            end,
+            bound: PhantomData,
+        }
+    }
+}
+
+impl<T, B: Bound> Range<T, B> {
+    ///Reinterpret the start and end values with a different `Bound`.
+    #[inline(always)]
+    pub fn into_bound<C: Bound>(self) -> Range<T, C> {
+        Range {
+            start: self.start,
+            end: self.end,
+            bound: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn new_bound(start: T, end: T) -> Range<T, B> {
+        Range {
+            start,
+            end,
+            bound: PhantomData,
+        }
     }
 }
 
@@ -31,17 +104,17 @@ impl<B> From<[B; 2]> for Range<B> {
     #[inline(always)]
     fn from(a: [B; 2]) -> Self {
         let [a, b] = a;
-        Range { start: a, end: b }
+        Range::new(a, b)
     }
 }
-impl<B> From<Range<B>> for [B; 2] {
+impl<B, C: Bound> From<Range<B, C>> for [B; 2] {
     #[inline(always)]
-    fn from(a: Range<B>) -> Self {
+    fn from(a: Range<B, C>) -> Self {
         [a.start, a.end]
     }
 }
 
-impl<T: Copy + Ord + core::ops::Sub<Output = T> + num_traits::sign::Signed> Range<T> {
+impl<T: Copy + Ord + core::ops::Sub<Output = T> + num_traits::sign::Signed, B: Bound> Range<T, B> {
     #[inline(always)]
     pub fn distance_to_point(&self, pos: T) -> Option<T> {
         if self.contains(pos) {
@@ -62,23 +135,35 @@ impl<T: Copy + Ord + core::ops::Sub<Output = T> + num_traits::sign::Signed> Rang
         }
     }
 }
-impl<T: Copy + PartialOrd> Range<T> {
+impl<T: Copy + PartialOrd, B: Bound> Range<T, B> {
     ///Like contains() but returns Ord.
-    ///If the pos is strictly less than the range.start, return less.
-    ///If the pos is greater of equal to the range.end, return greater.
+    ///If the pos is before the start of the range, return less.
+    ///If the pos is after the end of the range, return greater.
     ///else, return equal.
     #[inline(always)]
     pub fn contains_ext(&self, pos: T) -> core::cmp::Ordering {
-        if pos < self.start {
+        let before_start = if B::INCLUDES_START {
+            pos < self.start
+        } else {
+            pos <= self.start
+        };
+        let after_end = if B::INCLUDES_END {
+            pos > self.end
+        } else {
+            pos >= self.end
+        };
+
+        if before_start {
             core::cmp::Ordering::Less
-        } else if pos > self.end {
+        } else if after_end {
             core::cmp::Ordering::Greater
         } else {
             core::cmp::Ordering::Equal
         }
     }
 
-    ///Returns true if the point is inside of the range or on top of.
+    ///Returns true if the point is inside of the range.
+    ///With the default `Closed` bound, this includes being on top of the start or end.
     #[inline(always)]
     pub fn contains(&self, pos: T) -> bool {
         self.contains_ext(pos) == core::cmp::Ordering::Equal
@@ -86,19 +171,16 @@ impl<T: Copy + PartialOrd> Range<T> {
 
     ///Subdivides the range.
     ///No floating point calculations are done.
+    ///With `Closed` bounds the divider is inside both halves,
+    ///with `HalfOpen` bounds it is only inside the right half,
+    ///and with `Open` bounds it is inside neither.
     #[inline(always)]
-    pub fn subdivide(&self, divider: T) -> (Range<T>, Range<T>) {
+    pub fn subdivide(&self, divider: T) -> (Range<T, B>, Range<T, B>) {
         debug_assert!(self.start <= divider);
         debug_assert!(divider < self.end);
 
-        let l = Range {
-            start: self.start,
-            end: divider,
-        };
-        let r = Range {
-            start: divider,
-            end: self.end,
-        };
+        let l = Range::new_bound(self.start, divider);
+        let r = Range::new_bound(divider, self.end);
         (l, r)
     }
 
@@ -109,7 +191,7 @@ impl<T: Copy + PartialOrd> Range<T> {
     }
 
     #[inline(always)]
-    pub fn grow_to_fit(&mut self, b: &Range<T>) {
+    pub fn grow_to_fit(&mut self, b: &Range<T, B>) {
         let a = self;
         if b.start < a.start {
             a.start = b.start;
@@ -121,16 +203,34 @@ impl<T: Copy + PartialOrd> Range<T> {
 
     ///Returns true if self contains the specified range.
     #[inline(always)]
-    pub fn contains_range(&self, val: &Range<T>) -> bool {
+    pub fn contains_range(&self, val: &Range<T, B>) -> bool {
         self.start <= val.start && val.end <= self.end
     }
 
     ///Returns true if two ranges intersect.
     #[inline(always)]
-    pub fn intersects(&self, val: &Range<T>) -> bool {
-        !(self.end < val.start || val.end < self.start)
+    pub fn intersects(&self, val: &Range<T, B>) -> bool {
+        if B::INCLUDES_START && B::INCLUDES_END {
+            !(self.end < val.start || val.end < self.start)
+        } else {
+            self.start < val.end && val.start < self.end
+        }
         //self.contains(val.start) || val.contains(self.start)
     }
+
+    ///Creates a range that represents the intersection range.
+    ///Returns None exactly when `intersects` returns false.
+    #[inline(always)]
+    pub fn get_intersection(&self, val: &Range<T, B>) -> Option<Range<T, B>> {
+        if self.intersects(val) {
+            Some(Range::new_bound(
+                partial_min_max::max(self.start, val.start),
+                partial_min_max::min(self.end, val.end),
+            ))
+        } else {
+            None
+        }
+    }
 }
 
 #[cfg(test)]
@@ -150,6 +250,55 @@ mod tests {
         assert!(b.contains(6));
     }
 
+    #[test]
+    fn test_penetration() {
+        let a = Range::new(0, 5);
+        assert_eq!(a.penetration(&Range::new(4, 10)), Some(-1));
+        assert_eq!(a.penetration(&Range::new(-10, 2)), Some(2));
+        assert_eq!(a.penetration(&Range::new(5, 10)), Some(0));
+        assert_eq!(a.penetration(&Range::new(6, 10)), None);
+    }
+
+    #[test]
+    fn test_from_values() {
+        assert_eq!(Range::from_values([3, -2, 7, 0]), Some(Range::new(-2, 7)));
+        assert_eq!(Range::<i32>::from_values([]), None);
+
+        let r: Option<Range<i32>> = [Range::new(3, 4), Range::new(-2, 0)]
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(r, Some(Range::new(-2, 4)));
+        let r: Option<Range<i32>> = core::iter::empty().collect();
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let closed = Range::new(0, 5);
+        let half: Range<_, HalfOpen> = closed.into_bound();
+        let open: Range<_, Open> = closed.into_bound();
+
+        assert!(closed.contains(5) && !half.contains(5) && !open.contains(5));
+        assert!(closed.contains(0) && half.contains(0) && !open.contains(0));
+
+        //Touching ranges only intersect when both ends are closed.
+        let next = Range::new(5, 10);
+        assert!(closed.intersects(&next));
+        assert!(!half.intersects(&next.into_bound()));
+        assert!(!open.intersects(&next.into_bound()));
+        assert_eq!(closed.get_intersection(&next), Some(Range::new(5, 5)));
+        assert_eq!(half.get_intersection(&next.into_bound()), None);
+
+        //The divider is in both halves, the right half, or neither.
+        let (l, r) = closed.subdivide(2);
+        assert!(l.contains(2) && r.contains(2));
+        let (l, r) = half.subdivide(2);
+        assert!(!l.contains(2) && r.contains(2));
+        let (l, r) = open.subdivide(2);
+        assert!(!l.contains(2) && !r.contains(2));
+    }
+
     #[test]
     fn test_range() {
         let a = Range::new(0, 5);
@@ -157,14 +306,76 @@ mod tests {
         assert!(a.contains_range(&b))
     }
 }
-impl<T: Copy + core::ops::Sub<Output = T>> Range<T> {
+
+impl<T: Copy + PartialOrd> Range<T> {
+    ///Returns the smallest range that contains all the values,
+    ///or None if there are no values.
+    ///The orphan rules don't allow collecting plain values into an `Option<Range<T>>`, so use this instead.
+    #[inline]
+    pub fn from_values<I: IntoIterator<Item = T>>(values: I) -> Option<Range<T>> {
+        let mut values = values.into_iter();
+        let first = values.next()?;
+        let mut r = Range::new(first, first);
+        r.extend(values);
+        Some(r)
+    }
+
+    ///Returns the smallest range that contains all the ranges,
+    ///or None if there are no ranges.
+    #[inline]
+    pub fn union_all<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Option<Range<T>> {
+        let mut ranges = ranges.into_iter();
+        let mut r = ranges.next()?;
+        for a in ranges {
+            r.grow_to_fit(&a);
+        }
+        Some(r)
+    }
+}
+
+impl<T: Copy + PartialOrd, B: Bound> Extend<T> for Range<T, B> {
+    ///Grows the range to fit each value.
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        for a in values {
+            self.grow_to_fit(&Range::new_bound(a, a));
+        }
+    }
+}
+
+///Collects into the smallest range that contains all the ranges. None if there are no ranges.
+impl<T: Copy + PartialOrd> core::iter::FromIterator<Range<T>> for Option<Range<T>> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        Range::union_all(ranges)
+    }
+}
+
+impl<T: Copy + core::ops::Sub<Output = T>, B: Bound> Range<T, B> {
     #[inline(always)]
     pub fn distance(&self) -> T {
         self.end - self.start
     }
 }
 
-impl<T: Copy + core::ops::Sub<Output = T> + core::ops::Add<Output = T>> Range<T> {
+impl<T: Copy + PartialOrd + core::ops::Sub<Output = T>, B: Bound> Range<T, B> {
+    ///If the ranges intersect, returns the smallest amount this range has to be moved by
+    ///so that it only touches the other range.
+    ///Negative if it has to be moved towards the start, positive if towards the end.
+    #[inline(always)]
+    pub fn penetration(&self, other: &Range<T, B>) -> Option<T> {
+        if !self.intersects(other) {
+            return None;
+        }
+        if self.end - other.start < other.end - self.start {
+            Some(other.start - self.end)
+        } else {
+            Some(other.end - self.start)
+        }
+    }
+}
+
+impl<T: Copy + core::ops::Sub<Output = T> + core::ops::Add<Output = T>, B: Bound> Range<T, B> {
     #[inline(always)]
     pub fn grow(mut self, radius: T) -> Self {
         self.end = self.end + radius;
@@ -173,37 +384,34 @@ impl<T: Copy + core::ops::Sub<Output = T> + core::ops::Add<Output = T>> Range<T>
     }
 }
 
-impl<S> Range<S> {
+impl<S, C: Bound> Range<S, C> {
     #[inline(always)]
-    pub fn inner_as<B: 'static + Copy>(self) -> Range<B>
+    pub fn inner_as<B: 'static + Copy>(self) -> Range<B, C>
     where
         S: num_traits::AsPrimitive<B>,
     {
-        Range {
-            start: self.start.as_(),
-            end: self.end.as_(),
-        }
+        Range::new_bound(self.start.as_(), self.end.as_())
     }
 
     #[inline(always)]
-    pub fn inner_into<A>(self) -> Range<A>
+    pub fn inner_into<A>(self) -> Range<A, C>
     where
         S: Into<A>,
     {
         let start = self.start.into();
         let end = self.end.into();
-        Range { start, end }
+        Range::new_bound(start, end)
     }
 
     #[inline(always)]
-    pub fn inner_try_into<A>(self) -> Result<Range<A>, S::Error>
+    pub fn inner_try_into<A>(self) -> Result<Range<A, C>, S::Error>
     where
         S: TryInto<A>,
     {
         let start = self.start.try_into();
         let end = self.end.try_into();
         match (start, end) {
-            (Ok(start), Ok(end)) => Ok(Range { start, end }),
+            (Ok(start), Ok(end)) => Ok(Range::new_bound(start, end)),
             (Ok(_), Err(e)) => Err(e),
             (Err(e), Ok(_)) => Err(e),
             (Err(e1), Err(_)) => Err(e1),
@@ -215,23 +423,6 @@ impl<T: Copy + core::ops::Sub<Output = T> + core::ops::Add<Output = T>> Range<T>
     ///Create a range from a point and radius.
     #[inline(always)]
     pub fn from_point(point: T, radius: T) -> Range<T> {
-        Range {
-            start: point - radius,
-            end: point + radius,
-        }
-    }
-}
-
-impl<T: Copy + Ord> Range<T> {
-    ///Creates a range that represents the intersection range.
-    #[inline(always)]
-    pub fn get_intersection(&self, val: &Range<T>) -> Option<Range<T>> {
-        let a = self.start.max(val.start);
-        let b = self.end.min(val.end);
-        if a > b {
-            None
-        } else {
-            Some(Range { start: a, end: b })
-        }
+        Range::new(point - radius, point + radius)
     }
 }
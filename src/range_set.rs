@@ -0,0 +1,508 @@
+use crate::range::Range;
+use core::iter::Peekable;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+//All of the set operations below work on sorted iterators of non-overlapping ranges
+//and produce sorted non-overlapping ranges, so they can be chained without
+//allocating temporaries. This lets the fixed capacity set share them.
+//
+//Ranges are closed, so the difference of two sets is the closure of the true set difference.
+//For example `[0,10]` minus `[3,5]` is `[0,3]` and `[5,10]`.
+
+struct Union<T, I: Iterator<Item = Range<T>>, J: Iterator<Item = Range<T>>> {
+    a: Peekable<I>,
+    b: Peekable<J>,
+}
+
+impl<T: Copy + PartialOrd, I: Iterator<Item = Range<T>>, J: Iterator<Item = Range<T>>> Iterator
+    for Union<T, I, J>
+{
+    type Item = Range<T>;
+    fn next(&mut self) -> Option<Range<T>> {
+        let mut cur = match (self.a.peek(), self.b.peek()) {
+            (Some(a), Some(b)) => {
+                if a.start <= b.start {
+                    self.a.next()
+                } else {
+                    self.b.next()
+                }
+            }
+            (Some(_), None) => self.a.next(),
+            (None, _) => self.b.next(),
+        }?;
+
+        //Absorb any ranges that touch the current one.
+        loop {
+            let next = match self.a.next_if(|a| a.start <= cur.end) {
+                Some(a) => a,
+                None => match self.b.next_if(|b| b.start <= cur.end) {
+                    Some(b) => b,
+                    None => return Some(cur),
+                },
+            };
+            if next.end > cur.end {
+                cur.end = next.end;
+            }
+        }
+    }
+}
+
+struct Intersection<T, I: Iterator<Item = Range<T>>, J: Iterator<Item = Range<T>>> {
+    a: Peekable<I>,
+    b: Peekable<J>,
+}
+
+impl<T: Copy + PartialOrd, I: Iterator<Item = Range<T>>, J: Iterator<Item = Range<T>>> Iterator
+    for Intersection<T, I, J>
+{
+    type Item = Range<T>;
+    fn next(&mut self) -> Option<Range<T>> {
+        loop {
+            let a = *self.a.peek()?;
+            let b = *self.b.peek()?;
+
+            let inter = a.get_intersection(&b);
+
+            //Advance whichever range ends first, since it can't touch anything else.
+            if a.end < b.end {
+                self.a.next();
+            } else {
+                self.b.next();
+            }
+
+            if inter.is_some() {
+                return inter;
+            }
+        }
+    }
+}
+
+struct Difference<T, I: Iterator<Item = Range<T>>, J: Iterator<Item = Range<T>>> {
+    a: I,
+    b: Peekable<J>,
+    //What is left of the last range of a after cutting out a range of b.
+    cur: Option<Range<T>>,
+}
+
+impl<T: Copy + PartialOrd, I: Iterator<Item = Range<T>>, J: Iterator<Item = Range<T>>> Iterator
+    for Difference<T, I, J>
+{
+    type Item = Range<T>;
+    fn next(&mut self) -> Option<Range<T>> {
+        loop {
+            let a = match self.cur.take() {
+                Some(a) => a,
+                None => self.a.next()?,
+            };
+
+            //Zero length ranges have no interior to cut out.
+            while self
+                .b
+                .next_if(|b| b.end < a.start || b.start == b.end)
+                .is_some()
+            {}
+
+            match self.b.peek() {
+                Some(&b) if b.start <= a.end => {
+                    //Only consume b if it can't touch the next range of a.
+                    if b.end < a.end {
+                        self.b.next();
+                        self.cur = Some(Range::new(b.end, a.end));
+                    }
+                    if b.start > a.start {
+                        return Some(Range::new(a.start, b.start));
+                    }
+                }
+                _ => return Some(a),
+            }
+        }
+    }
+}
+
+#[inline(always)]
+fn union<T: Copy + PartialOrd>(
+    a: impl Iterator<Item = Range<T>>,
+    b: impl Iterator<Item = Range<T>>,
+) -> impl Iterator<Item = Range<T>> {
+    Union {
+        a: a.peekable(),
+        b: b.peekable(),
+    }
+}
+
+#[inline(always)]
+fn intersection<T: Copy + PartialOrd>(
+    a: impl Iterator<Item = Range<T>>,
+    b: impl Iterator<Item = Range<T>>,
+) -> impl Iterator<Item = Range<T>> {
+    Intersection {
+        a: a.peekable(),
+        b: b.peekable(),
+    }
+}
+
+#[inline(always)]
+fn difference<T: Copy + PartialOrd>(
+    a: impl Iterator<Item = Range<T>>,
+    b: impl Iterator<Item = Range<T>>,
+) -> impl Iterator<Item = Range<T>> {
+    Difference {
+        a,
+        b: b.peekable(),
+        cur: None,
+    }
+}
+
+#[inline(always)]
+fn symmetric_difference<'a, T: Copy + PartialOrd>(
+    a: &'a [Range<T>],
+    b: &'a [Range<T>],
+) -> impl Iterator<Item = Range<T>> + 'a {
+    difference(
+        union(a.iter().copied(), b.iter().copied()),
+        intersection(a.iter().copied(), b.iter().copied()),
+    )
+}
+
+#[inline(always)]
+fn contains<T: Copy + PartialOrd>(ranges: &[Range<T>], pos: T) -> bool {
+    ranges
+        .binary_search_by(|r| r.contains_ext(pos).reverse())
+        .is_ok()
+}
+
+#[inline(always)]
+fn total_length<T: Copy + core::ops::Sub<Output = T> + num_traits::Zero>(ranges: &[Range<T>]) -> T {
+    ranges.iter().fold(T::zero(), |acc, r| acc + r.distance())
+}
+
+///A set of sorted non-overlapping closed ranges.
+///Ranges that touch are merged together.
+#[cfg(feature = "alloc")]
+#[derive(Default, Hash, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub struct RangeSet<T> {
+    ranges: alloc::vec::Vec<Range<T>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> RangeSet<T> {
+    #[inline(always)]
+    pub fn new() -> RangeSet<T> {
+        RangeSet {
+            ranges: alloc::vec::Vec::new(),
+        }
+    }
+
+    ///The ranges in the set, sorted by their start.
+    #[inline(always)]
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Copy + PartialOrd> RangeSet<T> {
+    ///Creates a set containing only the specified range.
+    #[inline(always)]
+    pub fn from_range(range: Range<T>) -> RangeSet<T> {
+        RangeSet {
+            ranges: alloc::vec![range],
+        }
+    }
+
+    ///Adds the range to the set, merging it with any ranges that it touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        *self = self.union(&RangeSet::from_range(range));
+    }
+
+    ///Returns true if the point is inside of any range in the set.
+    #[inline(always)]
+    #[must_use]
+    pub fn contains(&self, pos: T) -> bool {
+        contains(&self.ranges, pos)
+    }
+
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        union(self.ranges.iter().copied(), other.ranges.iter().copied()).collect()
+    }
+
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        intersection(self.ranges.iter().copied(), other.ranges.iter().copied()).collect()
+    }
+
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        difference(self.ranges.iter().copied(), other.ranges.iter().copied()).collect()
+    }
+
+    pub fn symmetric_difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        symmetric_difference(&self.ranges, &other.ranges).collect()
+    }
+
+    ///Returns the parts of the bound that are not in the set.
+    pub fn complement(&self, bound: Range<T>) -> RangeSet<T> {
+        difference(core::iter::once(bound), self.ranges.iter().copied()).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Copy + core::ops::Sub<Output = T> + num_traits::Zero> RangeSet<T> {
+    ///Returns the sum of the lengths of all the ranges.
+    #[inline(always)]
+    #[must_use]
+    pub fn total_length(&self) -> T {
+        total_length(&self.ranges)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Copy + PartialOrd> core::iter::FromIterator<Range<T>> for RangeSet<T> {
+    ///The ranges can be in any order and may overlap.
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> RangeSet<T> {
+        let mut ranges: alloc::vec::Vec<_> = iter.into_iter().collect();
+
+        //Only sort if needed so that collecting the result of a set operation is cheap.
+        if ranges.windows(2).any(|w| w[1].start <= w[0].end) {
+            ranges.sort_unstable_by(|a, b| {
+                a.start
+                    .partial_cmp(&b.start)
+                    .unwrap_or(core::cmp::Ordering::Equal)
+            });
+            ranges = union(ranges.into_iter(), core::iter::empty()).collect();
+        }
+        RangeSet { ranges }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Copy + PartialOrd> Extend<Range<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        let other: RangeSet<T> = iter.into_iter().collect();
+        *self = self.union(&other);
+    }
+}
+
+///A set of sorted non-overlapping closed ranges that can hold at most `N` ranges.
+///Ranges that touch are merged together.
+///Operations that would need more than `N` ranges return `None`.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+#[must_use]
+pub struct ArrayRangeSet<T, const N: usize> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
+    ranges: [Range<T>; N],
+    len: usize,
+}
+
+impl<T: Default + Copy, const N: usize> Default for ArrayRangeSet<T, N> {
+    #[inline(always)]
+    fn default() -> Self {
+        ArrayRangeSet {
+            ranges: [Range::default(); N],
+            len: 0,
+        }
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for ArrayRangeSet<T, N> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.ranges() == other.ranges()
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for ArrayRangeSet<T, N> {}
+
+impl<T: core::hash::Hash, const N: usize> core::hash::Hash for ArrayRangeSet<T, N> {
+    #[inline(always)]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.ranges().hash(state);
+    }
+}
+
+impl<T, const N: usize> ArrayRangeSet<T, N> {
+    ///The ranges in the set, sorted by their start.
+    #[inline(always)]
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges[..self.len]
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T: Default + Copy + PartialOrd, const N: usize> ArrayRangeSet<T, N> {
+    #[inline(always)]
+    pub fn new() -> ArrayRangeSet<T, N> {
+        ArrayRangeSet::default()
+    }
+
+    ///Creates a set containing only the specified range.
+    ///Returns `None` if `N` is zero.
+    #[inline(always)]
+    pub fn from_range(range: Range<T>) -> Option<ArrayRangeSet<T, N>> {
+        Self::from_sorted(core::iter::once(range))
+    }
+
+    fn from_sorted(iter: impl Iterator<Item = Range<T>>) -> Option<ArrayRangeSet<T, N>> {
+        let mut set = ArrayRangeSet::default();
+        for r in iter {
+            *set.ranges.get_mut(set.len)? = r;
+            set.len += 1;
+        }
+        Some(set)
+    }
+
+    ///Adds the range to the set, merging it with any ranges that it touches.
+    ///Returns false and leaves the set unchanged if there is not enough room.
+    pub fn insert(&mut self, range: Range<T>) -> bool {
+        match Self::from_sorted(union(
+            self.ranges().iter().copied(),
+            core::iter::once(range),
+        )) {
+            Some(set) => {
+                *self = set;
+                true
+            }
+            None => false,
+        }
+    }
+
+    ///Returns true if the point is inside of any range in the set.
+    #[inline(always)]
+    #[must_use]
+    pub fn contains(&self, pos: T) -> bool {
+        contains(self.ranges(), pos)
+    }
+
+    pub fn union(&self, other: &ArrayRangeSet<T, N>) -> Option<ArrayRangeSet<T, N>> {
+        Self::from_sorted(union(
+            self.ranges().iter().copied(),
+            other.ranges().iter().copied(),
+        ))
+    }
+
+    pub fn intersection(&self, other: &ArrayRangeSet<T, N>) -> Option<ArrayRangeSet<T, N>> {
+        Self::from_sorted(intersection(
+            self.ranges().iter().copied(),
+            other.ranges().iter().copied(),
+        ))
+    }
+
+    pub fn difference(&self, other: &ArrayRangeSet<T, N>) -> Option<ArrayRangeSet<T, N>> {
+        Self::from_sorted(difference(
+            self.ranges().iter().copied(),
+            other.ranges().iter().copied(),
+        ))
+    }
+
+    pub fn symmetric_difference(&self, other: &ArrayRangeSet<T, N>) -> Option<ArrayRangeSet<T, N>> {
+        Self::from_sorted(symmetric_difference(self.ranges(), other.ranges()))
+    }
+
+    ///Returns the parts of the bound that are not in the set.
+    pub fn complement(&self, bound: Range<T>) -> Option<ArrayRangeSet<T, N>> {
+        Self::from_sorted(difference(
+            core::iter::once(bound),
+            self.ranges().iter().copied(),
+        ))
+    }
+}
+
+impl<T: Copy + core::ops::Sub<Output = T> + num_traits::Zero, const N: usize> ArrayRangeSet<T, N> {
+    ///Returns the sum of the lengths of all the ranges.
+    #[inline(always)]
+    #[must_use]
+    pub fn total_length(&self) -> T {
+        total_length(self.ranges())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::range;
+
+    #[test]
+    fn test_array_range_set() {
+        let mut a: ArrayRangeSet<i32, 4> = ArrayRangeSet::new();
+        assert!(a.insert(range(0, 5)));
+        assert!(a.insert(range(10, 15)));
+        assert!(a.insert(range(5, 7)));
+        assert_eq!(a.ranges(), &[range(0, 7), range(10, 15)]);
+        assert_eq!(a.total_length(), 12);
+
+        assert!(a.contains(7));
+        assert!(a.contains(10));
+        assert!(!a.contains(8));
+        assert!(!a.contains(-1));
+
+        let b = ArrayRangeSet::from_range(range(3, 12)).unwrap();
+        assert_eq!(a.union(&b).unwrap().ranges(), &[range(0, 15)]);
+        assert_eq!(
+            a.intersection(&b).unwrap().ranges(),
+            &[range(3, 7), range(10, 12)]
+        );
+        assert_eq!(
+            a.difference(&b).unwrap().ranges(),
+            &[range(0, 3), range(12, 15)]
+        );
+        assert_eq!(
+            a.symmetric_difference(&b).unwrap().ranges(),
+            &[range(0, 3), range(7, 10), range(12, 15)]
+        );
+        assert_eq!(
+            a.complement(range(-5, 20)).unwrap().ranges(),
+            &[range(-5, 0), range(7, 10), range(15, 20)]
+        );
+
+        let mut small: ArrayRangeSet<i32, 1> = ArrayRangeSet::new();
+        assert!(small.insert(range(0, 5)));
+        assert!(!small.insert(range(10, 15)));
+        assert_eq!(small.ranges(), &[range(0, 5)]);
+        assert_eq!(small.complement(range(-5, 20)), None);
+    }
+
+    #[test]
+    fn test_difference_edges() {
+        let d = |a: &[Range<i32>], b: &[Range<i32>]| {
+            let mut out = [Range::default(); 4];
+            let mut len = 0;
+            for r in difference(a.iter().copied(), b.iter().copied()) {
+                out[len] = r;
+                len += 1;
+            }
+            (out, len)
+        };
+
+        //Cutting out a single point does nothing.
+        let (out, len) = d(&[range(0, 10)], &[range(5, 5)]);
+        assert_eq!(&out[..len], &[range(0, 10)]);
+
+        //A point is removed by any range that contains it.
+        let (_, len) = d(&[range(5, 5)], &[range(0, 5)]);
+        assert_eq!(len, 0);
+
+        //One range of b cutting several ranges of a.
+        let (out, len) = d(&[range(0, 2), range(4, 6), range(8, 10)], &[range(1, 9)]);
+        assert_eq!(&out[..len], &[range(0, 1), range(9, 10)]);
+
+        let (_, len) = d(&[range(0, 10)], &[range(0, 10)]);
+        assert_eq!(len, 0);
+    }
+}
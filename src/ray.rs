@@ -1,6 +1,8 @@
 use crate::*;
 use core::cmp::Ordering;
 use core::convert::TryInto;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 ///Convenience function to create a ray.
 pub fn ray<N>(point: Vec2<N>, dir: Vec2<N>) -> Ray<N> {
@@ -9,6 +11,7 @@ pub fn ray<N>(point: Vec2<N>, dir: Vec2<N>) -> Ray<N> {
 
 ///A Ray.
 #[derive(Default,Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[must_use]
 pub struct Ray<N> {
     pub point: Vec2<N>,
@@ -74,12 +77,30 @@ impl<N: PartialOrd + Copy> Ray<N> {
 
 ///Describes if a ray hit a rectangle.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[must_use]
 pub enum CastResult<N> {
     Hit(N),
     NoHit,
 }
 
+///Detailed information about where a ray hit a shape.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub struct RayHit<N> {
+    ///The tval where the ray enters the shape.
+    pub t_enter: N,
+    ///The tval where the ray leaves the shape.
+    pub t_exit: N,
+    ///The point where the ray enters the shape.
+    pub point: Vec2<N>,
+    ///The outward facing normal of the shape at the hit point.
+    pub normal: Vec2<N>,
+    ///The axis of the side that was hit.
+    pub axis: AxisDyn,
+}
+
 impl<N> CastResult<N> {
     #[inline(always)]
     pub fn map<X>(self, mut func: impl FnMut(N) -> X) -> CastResult<X> {
@@ -98,14 +119,13 @@ impl<N> CastResult<N> {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "libm"))]
 pub mod foo {
     use super::*;
-    use roots;
-    use roots::*;
-    impl<N: num_traits::float::FloatCore + roots::FloatType> Ray<N> {
-        ///Checks if a ray intersects a circle.
-        pub fn cast_to_circle(&self, center: Vec2<N>, radius: N) -> CastResult<N> {
+    use num_traits::Float;
+    impl<N: Float> Ray<N> {
+        ///Returns the tvals where the ray crosses the circle, closest first.
+        fn circle_roots(&self, center: Vec2<N>, radius: N) -> Option<(N, N)> {
             //https://math.stackexchange.com/questions/311921/get-location-of-vector-circle-intersection
             //circle
             //(x-center.x)^2+(y-center.y)^2=r2
@@ -118,43 +138,100 @@ pub mod foo {
             //
             //we get:
             //
-            //𝑎𝑡^2+𝑏𝑡+𝑐=0
-            //
-            //
+            //𝑎𝑡^2+2𝑏𝑡+𝑐=0
             //
+            //where b is half of the usual linear coefficient.
             //
             let ray = self;
-            let zz = <N as FloatType>::zero();
-            let two = <N as FloatType>::one() + <N as FloatType>::one();
-
-            let a = ray.dir.x.powi(2) + ray.dir.y.powi(2);
-            let b = two * ray.dir.x * (ray.point.x - center.x)
-                + two * ray.dir.y * (ray.point.y - center.y);
-            let c = (ray.point.x - center.x).powi(2) + (ray.point.y - center.y).powi(2)
-                - radius.powi(2);
-
-            match find_roots_quadratic(a, b, c) {
-                Roots::No(_) => CastResult::NoHit,
-                Roots::One([a]) => {
-                    if a < zz {
-                        CastResult::NoHit
-                    } else {
-                        CastResult::Hit(a)
-                    }
-                }
-                Roots::Two([a, b]) => {
-                    let (closer, further) = if a < b { (a, b) } else { (b, a) };
+            let zz = N::zero();
+            let offset = ray.point - center;
+
+            let a = ray.dir.magnitude2();
+            let b = ray.dir.dot(offset);
+            let c = offset.magnitude2() - radius * radius;
+
+            if a == zz {
+                return None;
+            }
+
+            let disc = b * b - a * c;
+            if disc < zz {
+                return None;
+            }
+
+            //Avoid subtracting two numbers of similar magnitude.
+            //See "Numerical Recipes" section 5.6.
+            let q = if b < zz {
+                -b + disc.sqrt()
+            } else {
+                -b - disc.sqrt()
+            };
+
+            if q == zz {
+                //b and c are both zero, so the ray starts on the circle
+                //and moves tangent to it.
+                return Some((zz, zz));
+            }
+
+            let (t1, t2) = (q / a, c / q);
+            if t1 < t2 {
+                Some((t1, t2))
+            } else {
+                Some((t2, t1))
+            }
+        }
 
-                    if closer < zz && further < zz {
+        ///Checks if a ray intersects a circle.
+        ///If the ray starts inside the circle, the tval is zero.
+        pub fn cast_to_circle(&self, circle: &Circle<N>) -> CastResult<N> {
+            let zz = N::zero();
+            match self.circle_roots(circle.center, circle.radius) {
+                Some((closer, further)) => {
+                    if further < zz {
                         CastResult::NoHit
-                    } else if closer < zz && further > zz {
-                        CastResult::Hit(<N as FloatType>::zero())
+                    } else if closer < zz {
+                        CastResult::Hit(zz)
                     } else {
                         CastResult::Hit(closer)
                     }
                 }
-                _ => unreachable!(),
+                None => CastResult::NoHit,
+            }
+        }
+
+        ///Like `cast_to_circle` but also returns the exit tval, hit point and normal.
+        ///The normal is normalized. If the ray starts inside the circle,
+        ///`t_enter` is zero and the normal is the zero vector.
+        ///The axis is the axis the normal is most aligned with.
+        pub fn cast_to_circle_full(&self, circle: &Circle<N>) -> Option<RayHit<N>> {
+            let zz = N::zero();
+            let (closer, further) = self.circle_roots(circle.center, circle.radius)?;
+            if further < zz {
+                return None;
             }
+
+            let (t_enter, normal) = if closer < zz {
+                (zz, vec2(zz, zz))
+            } else {
+                (
+                    closer,
+                    (self.point_at_tval(closer) - circle.center) / circle.radius,
+                )
+            };
+
+            let axis = if normal.x.abs() >= normal.y.abs() {
+                AxisDyn::X
+            } else {
+                AxisDyn::Y
+            };
+
+            Some(RayHit {
+                t_enter,
+                t_exit: further,
+                point: self.point_at_tval(t_enter),
+                normal,
+                axis,
+            })
         }
     }
 }
@@ -202,9 +279,11 @@ impl<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy + core::fmt::De
             }
         }
     }
-    pub fn cast_to_rect(&self, rect: &Rect<N>) -> CastResult<N> {
+    ///Returns the tval and the axis of the side of the rect that was hit.
+    ///The axis is None if the ray starts inside of the rect.
+    fn cast_to_rect_side(&self, rect: &Rect<N>) -> CastResult<(N, Option<AxisDyn>)> {
         if rect.contains_point(self.point) {
-            return CastResult::Hit(N::zero());
+            return CastResult::Hit((N::zero(), None));
         }
         /*
         https://gamedev.stackexchange.com/questions/18436/most-efficient-aabb-vs-ray-collision-algorithms
@@ -216,10 +295,12 @@ impl<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy + core::fmt::De
             x: Range {
                 start: startx,
                 end: endx,
+                ..
             },
             y: Range {
                 start: starty,
                 end: endy,
+                ..
             },
         } = rect;
 
@@ -238,22 +319,89 @@ impl<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy + core::fmt::De
         let tval1 = self.cast_to_aaline(XAXIS, x);
         let tval2 = self.cast_to_aaline(YAXIS, y);
 
+        let xside = |t| (t, Some(AxisDyn::X));
+        let yside = |t| (t, Some(AxisDyn::Y));
+
         use CastResult::*;
         match (tval1, tval2) {
             (Hit(a), Hit(b)) => {
                 //xaxis hit
                 if a > b {
-                    self.prune_rect_axis(a, rect, YAXIS)
+                    self.prune_rect_axis(a, rect, YAXIS).map(xside)
                 } else {
-                    self.prune_rect_axis(b, rect, XAXIS)
+                    self.prune_rect_axis(b, rect, XAXIS).map(yside)
                 }
             }
-            (Hit(a), NoHit) => self.prune_rect_axis(a, rect, YAXIS),
-            (NoHit, Hit(b)) => self.prune_rect_axis(b, rect, XAXIS),
+            (Hit(a), NoHit) => self.prune_rect_axis(a, rect, YAXIS).map(xside),
+            (NoHit, Hit(b)) => self.prune_rect_axis(b, rect, XAXIS).map(yside),
             (NoHit, NoHit) => NoHit,
         }
     }
 
+    pub fn cast_to_rect(&self, rect: &Rect<N>) -> CastResult<N> {
+        self.cast_to_rect_side(rect).map(|(t, _)| t)
+    }
+
+    ///Casts a rect with the specified radius for each axis centered at the ray's point.
+    ///This is the same as casting the ray against the rect grown by the radius.
+    #[inline(always)]
+    pub fn cast_box_to_rect(&self, radius: Vec2<N>, rect: &Rect<N>) -> Option<RayHit<N>> {
+        self.cast_to_rect_full(&rect.grow_xy(radius))
+    }
+
+    ///Like `cast_to_rect` but also returns the exit tval, hit point and normal of the side that was hit.
+    ///If the ray starts inside the rect, `t_enter` is zero, the normal is the zero vector
+    ///and the axis is the axis of the side that the ray exits through.
+    pub fn cast_to_rect_full(&self, rect: &Rect<N>) -> Option<RayHit<N>> {
+        let (t_enter, side) = match self.cast_to_rect_side(rect) {
+            CastResult::Hit(a) => a,
+            CastResult::NoHit => return None,
+        };
+
+        //The exit is at the closest of the far planes.
+        let x = if self.dir.x >= N::zero() {
+            rect.x.end
+        } else {
+            rect.x.start
+        };
+
+        let y = if self.dir.y >= N::zero() {
+            rect.y.end
+        } else {
+            rect.y.start
+        };
+
+        use CastResult::*;
+        let (t_exit, exit_axis) =
+            match (self.cast_to_aaline(XAXIS, x), self.cast_to_aaline(YAXIS, y)) {
+                (Hit(a), Hit(b)) => {
+                    if a < b {
+                        (a, AxisDyn::X)
+                    } else {
+                        (b, AxisDyn::Y)
+                    }
+                }
+                (Hit(a), NoHit) => (a, AxisDyn::X),
+                (NoHit, Hit(b)) => (b, AxisDyn::Y),
+                //Degenerate ray with no direction.
+                (NoHit, NoHit) => (t_enter, AxisDyn::X),
+            };
+
+        let normal = match side {
+            Some(AxisDyn::X) => vec2(-self.dir.x.signum(), N::zero()),
+            Some(AxisDyn::Y) => vec2(N::zero(), -self.dir.y.signum()),
+            None => vec2(N::zero(), N::zero()),
+        };
+
+        Some(RayHit {
+            t_enter,
+            t_exit,
+            point: self.point_at_tval(t_enter),
+            normal,
+            axis: side.unwrap_or(exit_axis),
+        })
+    }
+
     /*
         pub fn find_candidate_planes(&self,rect:&Rect<N>)->[bool;4]{
             //In cases where the ray is directly vertical or horizant,
@@ -287,3 +435,143 @@ impl<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy + core::fmt::De
         }
     */
 }
+
+///Returns how many cells of the specified size it takes to cover the range, which is at least one.
+///The size must be positive.
+///Used by both `Grid` and `Ray::grid_traverse` so that they always agree on the number of cells.
+pub(crate) fn num_cells<N>(range: &Range<N>, size: N) -> usize
+where
+    N: num_traits::Num + PartialOrd + Copy + 'static + num_traits::AsPrimitive<usize>,
+    usize: num_traits::AsPrimitive<N>,
+{
+    use num_traits::AsPrimitive;
+
+    let dis = range.distance();
+    let n: usize = (dis / size).as_();
+    if n.as_() * size < dis || n == 0 {
+        n + 1
+    } else {
+        n
+    }
+}
+
+///An iterator over the cells of a uniform grid that a ray passes through, in order.
+///Created by `Ray::grid_traverse`.
+#[derive(Debug, Copy, Clone)]
+#[must_use]
+pub struct GridTraverse<N> {
+    step: Vec2<isize>,
+    dim: Vec2<isize>,
+    //The tvals at which the ray crosses into the next column and row.
+    t_max: Vec2<N>,
+    //How much the tval changes when crossing a whole column or row.
+    t_delta: Vec2<N>,
+    t_end: N,
+    next: Option<(Vec2<isize>, N, AxisDyn)>,
+}
+
+impl<N: num_traits::float::FloatCore> Iterator for GridTraverse<N> {
+    type Item = (Vec2<isize>, N, AxisDyn);
+    fn next(&mut self) -> Option<Self::Item> {
+        let cur = self.next.take()?;
+        let mut cell = cur.0;
+
+        let (t, axis) = if self.t_max.x < self.t_max.y {
+            let t = self.t_max.x;
+            cell.x += self.step.x;
+            self.t_max.x = t + self.t_delta.x;
+            (t, AxisDyn::X)
+        } else {
+            let t = self.t_max.y;
+            cell.y += self.step.y;
+            self.t_max.y = t + self.t_delta.y;
+            (t, AxisDyn::Y)
+        };
+
+        let inside = (0..self.dim.x).contains(&cell.x) && (0..self.dim.y).contains(&cell.y);
+        if t <= self.t_end && inside {
+            self.next = Some((cell, t, axis));
+        }
+        Some(cur)
+    }
+}
+
+impl<
+        N: num_traits::float::FloatCore
+            + num_traits::Signed
+            + core::fmt::Debug
+            + num_traits::AsPrimitive<isize>
+            + num_traits::AsPrimitive<usize>,
+    > Ray<N>
+where
+    isize: num_traits::AsPrimitive<N>,
+    usize: num_traits::AsPrimitive<N>,
+{
+    ///Walks every cell of a uniform grid that the ray passes through, in order, using the Amanatides–Woo algorithm.
+    ///The grid covers the bounds with cells of the specified size, with cell `(0,0)` at the top left of the bounds.
+    ///Yields the cell, the tval where the ray enters it and the axis of the side it entered through.
+    ///The first cell is the one the ray enters the bounds in, or starts in, and its axis is
+    ///the one returned by `cast_to_rect_full`. Stops once the ray leaves the bounds or passes `max_t`.
+    ///Panics if the cell size is not positive, like `Grid::new`.
+    pub fn grid_traverse(&self, cell_size: Vec2<N>, bounds: &Rect<N>, max_t: N) -> GridTraverse<N> {
+        use num_traits::AsPrimitive;
+
+        assert!(
+            cell_size.x > N::zero() && cell_size.y > N::zero(),
+            "the cell size of a grid must be positive"
+        );
+
+        let dim = vec2(
+            num_cells(&bounds.x, cell_size.x),
+            num_cells(&bounds.y, cell_size.y),
+        )
+        .inner_as::<isize>();
+
+        let mut traverse = GridTraverse {
+            step: vec2(0, 0),
+            dim,
+            t_max: vec2same(N::infinity()),
+            t_delta: vec2same(N::infinity()),
+            t_end: max_t,
+            next: None,
+        };
+
+        let hit = match self.cast_to_rect_full(bounds) {
+            Some(hit) if hit.t_enter <= max_t => hit,
+            _ => return traverse,
+        };
+        if hit.t_exit < traverse.t_end {
+            traverse.t_end = hit.t_exit;
+        }
+
+        let mut cell = vec2(0, 0);
+
+        macro_rules! macro_axis {
+            ($axis:ident) => {{
+                let start = bounds.get_range($axis).start;
+                let size = *cell_size.get_axis($axis);
+                let dir = *self.dir.get_axis($axis);
+                let point = *self.point.get_axis($axis);
+
+                //Clamp in case the entry point is slightly outside of the bounds from rounding.
+                let c: isize = ((*hit.point.get_axis($axis) - start) / size).floor().as_();
+                let c = c.max(0).min(*dim.get_axis($axis) - 1);
+                *cell.get_axis_mut($axis) = c;
+
+                if dir != N::zero() {
+                    let (step, boundary) = if dir > N::zero() { (1, c + 1) } else { (-1, c) };
+                    *traverse.step.get_axis_mut($axis) = step;
+                    *traverse.t_delta.get_axis_mut($axis) = size / dir.abs();
+                    *traverse.t_max.get_axis_mut($axis) =
+                        (start + boundary.as_() * size - point) / dir;
+                }
+            }};
+        }
+
+        macro_axis!(XAXIS);
+        macro_axis!(YAXIS);
+
+        traverse.next = Some((cell, hit.t_enter, hit.axis));
+        traverse
+    }
+}
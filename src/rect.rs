@@ -2,6 +2,8 @@ use crate::range::Range;
 use crate::vec2::vec2;
 use crate::*;
 use core::convert::TryInto;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 ///Convenience function to create a Rect.
 #[inline(always)]
@@ -10,16 +12,27 @@ pub fn rect<T>(xstart: T, xend: T, ystart: T, yend: T) -> Rect<T> {
 }
 
 ///An axis aligned rectangle. Stored as two Ranges.
+///Both ranges share the same `Bound`, which is `Closed` by default.
 #[derive(Default,Hash,Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[must_use]
-pub struct Rect<T> {
-    pub x: Range<T>,
-    pub y: Range<T>,
+pub struct Rect<T, B = Closed> {
+    pub x: Range<T, B>,
+    pub y: Range<T, B>,
 }
 
-impl<S> Rect<S> {
+impl<S, B: Bound> Rect<S, B> {
+    ///Reinterpret the ranges with a different `Bound`.
     #[inline(always)]
-    pub fn inner_into<A>(self) -> Rect<A>
+    pub fn into_bound<C: Bound>(self) -> Rect<S, C> {
+        Rect {
+            x: self.x.into_bound(),
+            y: self.y.into_bound(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn inner_into<A>(self) -> Rect<A, B>
     where
         S: Into<A>,
     {
@@ -30,7 +43,7 @@ impl<S> Rect<S> {
     }
 
     #[inline(always)]
-    pub fn inner_try_into<A>(self) -> Result<Rect<A>, S::Error>
+    pub fn inner_try_into<A>(self) -> Result<Rect<A, B>, S::Error>
     where
         S: TryInto<A>,
     {
@@ -63,24 +76,24 @@ impl<B> From<[B; 4]> for Rect<B> {
     }
 }
 
-impl<B> From<Rect<B>> for [B; 4] {
+impl<B, C: Bound> From<Rect<B, C>> for [B; 4] {
     #[inline(always)]
-    fn from(a: Rect<B>) -> Self {
+    fn from(a: Rect<B, C>) -> Self {
         [a.x.start, a.x.end, a.y.start, a.y.end]
     }
 }
 
-impl<B: Copy> From<&Rect<B>> for [B; 4] {
+impl<B: Copy, C: Bound> From<&Rect<B, C>> for [B; 4] {
     #[inline(always)]
-    fn from(a: &Rect<B>) -> Self {
+    fn from(a: &Rect<B, C>) -> Self {
         [a.x.start, a.x.end, a.y.start, a.y.end]
     }
 }
 
-impl<T> Rect<T> {
+impl<T, B: Bound> Rect<T, B> {
     ///Get the range of one axis.
     #[inline(always)]
-    pub fn get_range(&self, axis: impl Axis) -> &Range<T> {
+    pub fn get_range(&self, axis: impl Axis) -> &Range<T, B> {
         if axis.is_xaxis() {
             &self.x
         } else {
@@ -90,7 +103,7 @@ impl<T> Rect<T> {
 
     ///Get the mutable range of one axis.
     #[inline(always)]
-    pub fn get_range_mut(&mut self, axis: impl Axis) -> &mut Range<T> {
+    pub fn get_range_mut(&mut self, axis: impl Axis) -> &mut Range<T, B> {
         if axis.is_xaxis() {
             &mut self.x
         } else {
@@ -106,19 +119,13 @@ impl<T> Rect<T> {
     #[inline(always)]
     pub fn new(xstart: T, xend: T, ystart: T, yend: T) -> Rect<T> {
         Rect {
-            x: Range {
-                start: xstart,
-                end: xend,
-            },
-            y: Range {
-                start: ystart,
-                end: yend,
-            },
+            x: Range::new(xstart, xend),
+            y: Range::new(ystart, yend),
         }
     }
 }
 
-impl<T: Copy> Rect<T> {
+impl<T: Copy, B: Bound> Rect<T, B> {
     #[inline(always)]
     pub fn top_left(&self) -> Vec2<T> {
         vec2(self.x.start, self.y.start)
@@ -139,9 +146,9 @@ impl<T: Copy> Rect<T> {
     }
 
     #[inline(always)]
-    pub fn inner_as<B: 'static + Copy>(&self) -> Rect<B>
+    pub fn inner_as<C: 'static + Copy>(&self) -> Rect<C, B>
     where
-        T: num_traits::AsPrimitive<B>,
+        T: num_traits::AsPrimitive<C>,
     {
         Rect {
             x: self.x.inner_as(),
@@ -158,7 +165,7 @@ impl<T: Copy> Rect<T> {
     }
 }
 
-impl<T: PartialOrd + Copy> Rect<T> {
+impl<T: PartialOrd + Copy, B: Bound> Rect<T, B> {
     ///Returns true if the point is contained in the the ranges of both axis.
     #[inline(always)]
     pub fn contains_point(&self, a: Vec2<T>) -> bool {
@@ -166,7 +173,7 @@ impl<T: PartialOrd + Copy> Rect<T> {
     }
 }
 
-impl<T: Copy + core::ops::Sub<Output = T> + core::ops::Add<Output = T>> Rect<T> {
+impl<T: Copy + core::ops::Sub<Output = T> + core::ops::Add<Output = T>, B: Bound> Rect<T, B> {
     ///Grow a rectangle of a radius.
     #[inline(always)]
     pub fn grow(self, radius: T) -> Self {
@@ -175,6 +182,24 @@ impl<T: Copy + core::ops::Sub<Output = T> + core::ops::Add<Output = T>> Rect<T>
             y: self.y.grow(radius),
         }
     }
+
+    ///Move a rectangle by an offset.
+    #[inline(always)]
+    pub fn translate(self, offset: Vec2<T>) -> Self {
+        Rect {
+            x: Range::new_bound(self.x.start + offset.x, self.x.end + offset.x),
+            y: Range::new_bound(self.y.start + offset.y, self.y.end + offset.y),
+        }
+    }
+
+    ///Grow a rectangle with a different radius for each axis.
+    #[inline(always)]
+    pub fn grow_xy(self, radius: Vec2<T>) -> Self {
+        Rect {
+            x: self.x.grow(radius.x),
+            y: self.y.grow(radius.y),
+        }
+    }
 }
 
 impl<
@@ -183,7 +208,8 @@ impl<
             + core::ops::Sub<Output = T>
             + core::ops::Mul<Output = T>
             + core::ops::Add<Output = T>,
-    > Rect<T>
+        B: Bound,
+    > Rect<T, B>
 {
     ///If the point is outside the rectangle, returns the squared distance from the closest corner of the rectangle.
     ///If the point is inside the rectangle, it will return None.
@@ -235,7 +261,7 @@ impl<
     }
 }
 
-impl<T: num_traits::Num + Copy> Rect<T> {
+impl<T: num_traits::Num + Copy, B: Bound> Rect<T, B> {
     #[inline(always)]
     pub fn derive_center(&self) -> Vec2<T> {
         let two = T::one() + T::one();
@@ -244,14 +270,15 @@ impl<T: num_traits::Num + Copy> Rect<T> {
     }
 }
 
-impl<T: PartialOrd + Copy> Rect<T> {
+impl<T: PartialOrd + Copy, B: Bound> Rect<T, B> {
     ///Subdivides the rectangle.
     ///No floating point calculations are done.
-    ///Important to note that a point that was in the original rectangle,
+    ///Important to note that with the default `Closed` bound, a point that was in the original rectangle,
     ///could actually be inside both subdivded rectangles.
     ///This is because the ranges are inclusive on both sides `[start,end]`.
+    ///Use `HalfOpen` ranges if every point should be in exactly one half.
     #[inline(always)]
-    pub fn subdivide<A: Axis>(&self, axis: A, divider: T) -> (Rect<T>, Rect<T>) {
+    pub fn subdivide<A: Axis>(&self, axis: A, divider: T) -> (Rect<T, B>, Rect<T, B>) {
         let ca = axis;
         let na = axis.next();
 
@@ -293,7 +320,7 @@ impl<T: PartialOrd + Copy> Rect<T> {
 
     ///Returns true if the specified rect is inside of this rect.
     #[inline(always)]
-    pub fn contains_rect(&self, rect: &Rect<T>) -> bool {
+    pub fn contains_rect(&self, rect: &Rect<T, B>) -> bool {
         self.x.contains_range(&rect.x) && self.y.contains_range(&rect.y)
     }
 
@@ -316,7 +343,7 @@ impl<T: PartialOrd + Copy> Rect<T> {
     ///Grow the rectangle to fit the specified rectangle by replacing values
     ///with the specified rectangle. No floating point computations.
     #[inline(always)]
-    pub fn grow_to_fit(&mut self, rect: &Rect<T>) -> &mut Self {
+    pub fn grow_to_fit(&mut self, rect: &Rect<T, B>) -> &mut Self {
         {
             macro_rules! macro_axis {
                 ($axis:ident) => {{
@@ -333,36 +360,205 @@ impl<T: PartialOrd + Copy> Rect<T> {
     }
 
     #[inline(always)]
-    pub fn intersects_rect(&self, other: &Rect<T>) -> bool {
+    pub fn intersects_rect(&self, other: &Rect<T, B>) -> bool {
         other.x.intersects(&self.x) && other.y.intersects(&self.y)
     }
-}
 
-impl<T: PartialOrd + Copy> Rect<T> {
     ///Get an intersecting rectangle.
     ///No floating point calculations as the new rectangle is made up of
     ///values from this rectangle and the specified rectangle.
+    ///Returns None exactly when `intersects_rect` returns false.
+    ///With `Closed` bounds, rects that only touch return a rect with no width or height.
     #[inline(always)]
-    pub fn get_intersect_rect(&self, other: &Rect<T>) -> Option<Rect<T>> {
-        macro_rules! macro_axis {
-            ($axis:ident) => {{
-                let xr = other.get_range($axis);
-                let xf = self.get_range($axis);
-
-                let range = Range {
-                    start: partial_min_max::max(xr.start, xf.start),
-                    end: partial_min_max::min(xr.end, xf.end),
-                };
-
-                if range.end <= range.start {
-                    return None;
+    pub fn get_intersect_rect(&self, other: &Rect<T, B>) -> Option<Rect<T, B>> {
+        let x = self.x.get_intersection(&other.x)?;
+        let y = self.y.get_intersection(&other.y)?;
+        Some(Rect { x, y })
+    }
+
+    ///Returns the parts of this rect that are not covered by the other rect.
+    ///Yields at most four rects that only share edges.
+    ///If the rects only touch, this rect is returned unchanged.
+    ///
+    ///The left and right columns span the full height of this rect.
+    ///The bottom and top pieces span only the width of the intersection.
+    pub fn difference(&self, other: &Rect<T, B>) -> impl Iterator<Item = Rect<T, B>> {
+        let mut pieces = [None; 4];
+        match self.get_intersect_rect(other) {
+            Some(inter) if inter.x.start < inter.x.end && inter.y.start < inter.y.end => {
+                let mut rest = *self;
+                if rest.x.start < inter.x.start {
+                    let (l, r) = rest.subdivide(XAXIS, inter.x.start);
+                    pieces[0] = Some(l);
+                    rest = r;
+                }
+                if inter.x.end < rest.x.end {
+                    let (l, r) = rest.subdivide(XAXIS, inter.x.end);
+                    pieces[1] = Some(r);
+                    rest = l;
                 }
-                range
-            }};
+                if rest.y.start < inter.y.start {
+                    let (l, r) = rest.subdivide(YAXIS, inter.y.start);
+                    pieces[2] = Some(l);
+                    rest = r;
+                }
+                if inter.y.end < rest.y.end {
+                    let (_, r) = rest.subdivide(YAXIS, inter.y.end);
+                    pieces[3] = Some(r);
+                }
+            }
+            _ => pieces[0] = Some(*self),
+        }
+        IntoIterator::into_iter(pieces).flatten()
+    }
+}
+
+impl<T: num_traits::float::FloatCore + num_traits::Signed + core::fmt::Debug> Rect<T> {
+    ///Finds when this rect, moving by velocity, first touches the other rect.
+    ///
+    ///`t_enter` is the time of impact as a fraction of velocity, and `point` is where
+    ///this rect's top left corner is at that time. The normal is that of the side of
+    ///the other rect that was hit. Returns None if the rects do not touch within one velocity step.
+    ///If the rects already touch, `t_enter` is zero and the normal is the zero vector.
+    ///
+    ///This is a ray cast from the top left corner against the other rect
+    ///expanded by this rect's size. See `Ray::cast_box_to_rect` for casting from the center.
+    pub fn sweep_to_rect(&self, velocity: Vec2<T>, other: &Rect<T>) -> Option<RayHit<T>> {
+        let expanded = Rect::new(
+            other.x.start - self.x.distance(),
+            other.x.end,
+            other.y.start - self.y.distance(),
+            other.y.end,
+        );
+
+        let hit = ray(self.top_left(), velocity).cast_to_rect_full(&expanded)?;
+        if hit.t_enter > T::one() {
+            None
+        } else {
+            Some(hit)
         }
+    }
+}
 
-        let x = macro_axis!(XAXIS);
-        let y = macro_axis!(YAXIS);
-        Some(Rect { x, y })
+impl<T: num_traits::Signed + PartialOrd + Copy, B: Bound> Rect<T, B> {
+    ///If the rects intersect, returns the minimum translation vector that moves this rect
+    ///so that it only touches the other rect, along with the axis it is on.
+    ///The axis is the one with the least overlap. Ties favor the x axis.
+    pub fn penetration(&self, other: &Rect<T, B>) -> Option<(Vec2<T>, AxisDyn)> {
+        let px = self.x.penetration(&other.x)?;
+        let py = self.y.penetration(&other.y)?;
+
+        if px.abs() <= py.abs() {
+            Some((vec2(px, T::zero()), AxisDyn::X))
+        } else {
+            Some((vec2(T::zero(), py), AxisDyn::Y))
+        }
+    }
+
+    ///Pushes this rect out of each of the static rects in order,
+    ///using the minimum translation vector each time.
+    ///Returns the total offset that was applied.
+    ///
+    ///Pushing out of one rect can push this rect into a rect that was already handled,
+    ///so crowded scenes may need this called more than once.
+    pub fn resolve_penetrations(&mut self, statics: &[Rect<T, B>]) -> Vec2<T> {
+        let mut total = vec2(T::zero(), T::zero());
+        for other in statics {
+            if let Some((offset, _)) = self.penetration(other) {
+                *self = self.translate(offset);
+                total = total + offset;
+            }
+        }
+        total
+    }
+}
+
+impl<T: PartialOrd + Copy> Rect<T> {
+    ///Returns the smallest rect that contains all the points,
+    ///or None if there are no points.
+    #[inline]
+    pub fn from_points<I: IntoIterator<Item = Vec2<T>>>(points: I) -> Option<Rect<T>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut r = Rect::new(first.x, first.x, first.y, first.y);
+        r.extend(points);
+        Some(r)
+    }
+
+    ///Returns the smallest rect that contains all the rects,
+    ///or None if there are no rects.
+    #[inline]
+    pub fn union_all<I: IntoIterator<Item = Rect<T>>>(rects: I) -> Option<Rect<T>> {
+        let mut rects = rects.into_iter();
+        let mut r = rects.next()?;
+        r.extend(rects);
+        Some(r)
+    }
+}
+
+impl<T: PartialOrd + Copy, B: Bound> Extend<Vec2<T>> for Rect<T, B> {
+    ///Grows the rect to fit each point.
+    #[inline]
+    fn extend<I: IntoIterator<Item = Vec2<T>>>(&mut self, points: I) {
+        for p in points {
+            self.grow_to_fit_point(p);
+        }
+    }
+}
+
+impl<T: PartialOrd + Copy, B: Bound> Extend<Rect<T, B>> for Rect<T, B> {
+    ///Grows the rect to fit each rect.
+    #[inline]
+    fn extend<I: IntoIterator<Item = Rect<T, B>>>(&mut self, rects: I) {
+        for r in rects {
+            self.grow_to_fit(&r);
+        }
+    }
+}
+
+///Collects into the smallest rect that contains all the points. None if there are no points.
+impl<T: PartialOrd + Copy> core::iter::FromIterator<Vec2<T>> for Option<Rect<T>> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Vec2<T>>>(points: I) -> Self {
+        Rect::from_points(points)
+    }
+}
+
+///Collects into the smallest rect that contains all the rects. None if there are no rects.
+impl<T: PartialOrd + Copy> core::iter::FromIterator<Rect<T>> for Option<Rect<T>> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Rect<T>>>(rects: I) -> Self {
+        Rect::union_all(rects)
+    }
+}
+
+///Implemented by anything that has a bounding rect.
+///The spatial data structures use this to get the rect of each of their items.
+pub trait HasRect {
+    type Num;
+    fn get_rect(&self) -> &Rect<Self::Num>;
+}
+
+impl<N> HasRect for Rect<N> {
+    type Num = N;
+    #[inline(always)]
+    fn get_rect(&self) -> &Rect<N> {
+        self
+    }
+}
+
+impl<N, T> HasRect for (Rect<N>, T) {
+    type Num = N;
+    #[inline(always)]
+    fn get_rect(&self) -> &Rect<N> {
+        &self.0
+    }
+}
+
+impl<X: HasRect> HasRect for &X {
+    type Num = X::Num;
+    #[inline(always)]
+    fn get_rect(&self) -> &Rect<X::Num> {
+        X::get_rect(self)
     }
 }
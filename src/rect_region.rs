@@ -0,0 +1,132 @@
+use crate::*;
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+///A region of space made up of rects that only share edges.
+///The region is treated as an area, so rects with no area are ignored.
+#[derive(Default, Hash, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub struct RectRegion<T, B = Closed> {
+    rects: Vec<Rect<T, B>>,
+}
+
+impl<T, B: Bound> RectRegion<T, B> {
+    #[inline(always)]
+    pub fn new() -> RectRegion<T, B> {
+        RectRegion { rects: Vec::new() }
+    }
+
+    ///The rects that make up the region. They are in no particular order.
+    #[inline(always)]
+    pub fn rects(&self) -> &[Rect<T, B>] {
+        &self.rects
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+}
+
+impl<T: PartialOrd + Copy, B: Bound> RectRegion<T, B> {
+    #[inline(always)]
+    fn has_area(rect: &Rect<T, B>) -> bool {
+        rect.x.start < rect.x.end && rect.y.start < rect.y.end
+    }
+
+    ///Creates a region covering the specified rect.
+    #[inline(always)]
+    pub fn from_rect(rect: Rect<T, B>) -> RectRegion<T, B> {
+        let mut r = RectRegion::new();
+        r.insert(rect);
+        r
+    }
+
+    ///Returns true if the point is inside of any rect in the region.
+    #[inline(always)]
+    #[must_use]
+    pub fn contains_point(&self, point: Vec2<T>) -> bool {
+        self.rects.iter().any(|r| r.contains_point(point))
+    }
+
+    ///Adds the parts of the rect that are not already in the region.
+    pub fn insert(&mut self, rect: Rect<T, B>) {
+        if !Self::has_area(&rect) {
+            return;
+        }
+        let mut pieces = alloc::vec![rect];
+        for a in self.rects.iter() {
+            pieces = pieces.iter().flat_map(|p| p.difference(a)).collect();
+        }
+        self.rects.extend(pieces);
+    }
+
+    ///Cuts the rect out of the region.
+    pub fn remove(&mut self, rect: &Rect<T, B>) {
+        self.rects = self.rects.iter().flat_map(|a| a.difference(rect)).collect();
+    }
+
+    pub fn union(&self, other: &RectRegion<T, B>) -> RectRegion<T, B> {
+        let mut r = self.clone();
+        r.extend(other.rects.iter().copied());
+        r
+    }
+
+    pub fn intersection(&self, other: &RectRegion<T, B>) -> RectRegion<T, B> {
+        let rects = self
+            .rects
+            .iter()
+            .flat_map(|a| {
+                other
+                    .rects
+                    .iter()
+                    .filter_map(move |b| a.get_intersect_rect(b))
+            })
+            .filter(Self::has_area)
+            .collect();
+        RectRegion { rects }
+    }
+
+    pub fn subtract(&self, other: &RectRegion<T, B>) -> RectRegion<T, B> {
+        let mut r = self.clone();
+        for b in other.rects.iter() {
+            r.remove(b);
+        }
+        r
+    }
+}
+
+impl<
+        T: Copy + core::ops::Sub<Output = T> + core::ops::Mul<Output = T> + num_traits::Zero,
+        B: Bound,
+    > RectRegion<T, B>
+{
+    ///Returns the total area covered by the region.
+    #[inline(always)]
+    #[must_use]
+    pub fn area(&self) -> T {
+        self.rects
+            .iter()
+            .fold(T::zero(), |acc, r| acc + r.x.distance() * r.y.distance())
+    }
+}
+
+impl<T: PartialOrd + Copy, B: Bound> Extend<Rect<T, B>> for RectRegion<T, B> {
+    fn extend<I: IntoIterator<Item = Rect<T, B>>>(&mut self, iter: I) {
+        for r in iter {
+            self.insert(r);
+        }
+    }
+}
+
+///The rects may overlap.
+impl<T: PartialOrd + Copy, B: Bound> core::iter::FromIterator<Rect<T, B>> for RectRegion<T, B> {
+    fn from_iter<I: IntoIterator<Item = Rect<T, B>>>(iter: I) -> RectRegion<T, B> {
+        let mut r = RectRegion::new();
+        r.extend(iter);
+        r
+    }
+}
@@ -0,0 +1,373 @@
+use crate::range::Range;
+use crate::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+///Convenience function to create a Segment.
+#[inline(always)]
+pub fn segment<N>(a: Vec2<N>, b: Vec2<N>) -> Segment<N> {
+    Segment { a, b }
+}
+
+///A finite line segment between two points.
+#[derive(Default, Hash, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub struct Segment<N> {
+    pub a: Vec2<N>,
+    pub b: Vec2<N>,
+}
+
+///Describes how two segments intersect.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub enum SegmentIntersection<N> {
+    ///The segments cross or touch at a single point.
+    Point(Vec2<N>),
+    ///The segments are collinear and share this segment.
+    Overlap(Segment<N>),
+    NoIntersection,
+}
+
+impl<N: Copy> Segment<N> {
+    #[inline(always)]
+    pub fn inner_as<B: 'static + Copy>(&self) -> Segment<B>
+    where
+        N: num_traits::AsPrimitive<B>,
+    {
+        segment(self.a.inner_as(), self.b.inner_as())
+    }
+}
+
+impl<N: PartialOrd + Copy> Segment<N> {
+    ///Returns the smallest rect that contains both end points.
+    #[inline(always)]
+    pub fn bounding_rect(&self) -> Rect<N> {
+        let mut r = rect(self.a.x, self.a.x, self.a.y, self.a.y);
+        r.grow_to_fit_point(self.b);
+        r
+    }
+}
+
+///A fraction with a positive denominator.
+///Used so that segment calculations work for integers
+///without truncating intermediate t values.
+#[derive(Copy, Clone)]
+struct Frac<N> {
+    num: N,
+    den: N,
+}
+
+impl<N: num_traits::Num + PartialOrd + Copy> Frac<N> {
+    #[inline(always)]
+    fn less_than(self, other: Frac<N>) -> bool {
+        self.num * other.den < other.num * self.den
+    }
+}
+
+impl<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy> Segment<N> {
+    #[inline(always)]
+    fn lerp(&self, t: Frac<N>) -> Vec2<N> {
+        self.a + ((self.b - self.a) * t.num) / t.den
+    }
+
+    ///Returns the point on the segment closest to the specified point.
+    pub fn closest_point(&self, point: Vec2<N>) -> Vec2<N> {
+        let r = self.b - self.a;
+        let num = (point - self.a).dot(r);
+        let den = r.magnitude2();
+
+        if num <= N::zero() || den == N::zero() {
+            self.a
+        } else if num >= den {
+            self.b
+        } else {
+            self.lerp(Frac { num, den })
+        }
+    }
+
+    ///Returns the squared distance from the point to the closest point on the segment.
+    #[inline(always)]
+    #[must_use]
+    pub fn distance_squared_to_point(&self, point: Vec2<N>) -> N {
+        self.closest_point(point).distance_squared_to_point(point)
+    }
+
+    ///Finds where two segments intersect.
+    pub fn intersect(&self, other: &Segment<N>) -> SegmentIntersection<N> {
+        //https://stackoverflow.com/questions/563198/how-do-you-detect-where-two-line-segments-intersect
+        let r = self.b - self.a;
+        let s = other.b - other.a;
+        let qp = other.a - self.a;
+
+        let denom = r.cross(s);
+
+        if denom == N::zero() {
+            if qp.cross(r) != N::zero() || qp.cross(s) != N::zero() {
+                //Parallel but not on the same line.
+                return SegmentIntersection::NoIntersection;
+            }
+            return self.intersect_collinear(other);
+        }
+
+        //Flip so that the denominator is positive.
+        let (tn, un, denom) = if denom < N::zero() {
+            (-qp.cross(s), -qp.cross(r), -denom)
+        } else {
+            (qp.cross(s), qp.cross(r), denom)
+        };
+
+        let unit = Range::new(N::zero(), denom);
+        if unit.contains(tn) && unit.contains(un) {
+            SegmentIntersection::Point(self.lerp(Frac {
+                num: tn,
+                den: denom,
+            }))
+        } else {
+            SegmentIntersection::NoIntersection
+        }
+    }
+
+    fn intersect_collinear(&self, other: &Segment<N>) -> SegmentIntersection<N> {
+        //A segment with no length is a single point on the line of the other segment,
+        //so it only intersects if it is between the other segment's end points.
+        for (point, seg) in [(self, other), (other, self)] {
+            if point.a == point.b {
+                return if seg.bounding_rect().contains_point(point.a) {
+                    SegmentIntersection::Point(point.a)
+                } else {
+                    SegmentIntersection::NoIntersection
+                };
+            }
+        }
+
+        //All four points are on one line, so compare them along
+        //whichever axis the line is not perpendicular to.
+        let vertical = self.a.x == self.b.x && other.a.x == other.b.x;
+        let val = |v: Vec2<N>| if vertical { v.y } else { v.x };
+        let sorted = |s: &Segment<N>| {
+            if val(s.a) <= val(s.b) {
+                (s.a, s.b)
+            } else {
+                (s.b, s.a)
+            }
+        };
+
+        let (lo1, hi1) = sorted(self);
+        let (lo2, hi2) = sorted(other);
+
+        let start = if val(lo1) < val(lo2) { lo2 } else { lo1 };
+        let end = if val(hi1) < val(hi2) { hi1 } else { hi2 };
+
+        if val(end) < val(start) {
+            SegmentIntersection::NoIntersection
+        } else if start == end {
+            SegmentIntersection::Point(start)
+        } else {
+            SegmentIntersection::Overlap(segment(start, end))
+        }
+    }
+
+    ///Returns true if any part of the segment is inside of the rect.
+    #[inline(always)]
+    pub fn intersects_rect(&self, rect: &Rect<N>) -> bool {
+        self.clip_to_rect(rect).is_some()
+    }
+
+    ///Returns the part of the segment that is inside of the rect.
+    ///Uses the Liang–Barsky algorithm.
+    pub fn clip_to_rect(&self, rect: &Rect<N>) -> Option<Segment<N>> {
+        let d = self.b - self.a;
+
+        let mut start = Frac {
+            num: N::zero(),
+            den: N::one(),
+        };
+        let mut end = Frac {
+            num: N::one(),
+            den: N::one(),
+        };
+
+        macro_rules! macro_axis {
+            ($axis:ident) => {{
+                let range = rect.get_range($axis);
+                let p = *self.a.get_axis($axis);
+                let dp = *d.get_axis($axis);
+
+                if dp == N::zero() {
+                    if !range.contains(p) {
+                        return None;
+                    }
+                } else {
+                    //The t values at which the segment crosses the start and end of the range.
+                    let (enter, exit) = if dp > N::zero() {
+                        (range.start - p, range.end - p)
+                    } else {
+                        (p - range.end, p - range.start)
+                    };
+                    let den = dp.abs();
+
+                    let enter = Frac { num: enter, den };
+                    let exit = Frac { num: exit, den };
+                    if start.less_than(enter) {
+                        start = enter;
+                    }
+                    if exit.less_than(end) {
+                        end = exit;
+                    }
+                }
+            }};
+        }
+
+        macro_axis!(XAXIS);
+        macro_axis!(YAXIS);
+
+        if end.less_than(start) {
+            None
+        } else {
+            Some(segment(self.lerp(start), self.lerp(end)))
+        }
+    }
+}
+
+impl<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy> Ray<N> {
+    ///Checks if a ray intersects a segment.
+    ///If the ray is collinear with the segment, the closest end point ahead of the ray is returned.
+    pub fn cast_to_segment(&self, seg: &Segment<N>) -> CastResult<N> {
+        let s = seg.b - seg.a;
+        let qp = seg.a - self.point;
+
+        let denom = self.dir.cross(s);
+
+        if denom == N::zero() {
+            if qp.cross(self.dir) != N::zero() {
+                return CastResult::NoHit;
+            }
+
+            //Collinear. Find the tvals of each end of the segment.
+            let d2 = self.dir.magnitude2();
+            if d2 == N::zero() {
+                return CastResult::NoHit;
+            }
+            let ta = qp.dot(self.dir) / d2;
+            let tb = (seg.b - self.point).dot(self.dir) / d2;
+            let (closer, further) = if ta < tb { (ta, tb) } else { (tb, ta) };
+
+            return if further < N::zero() {
+                CastResult::NoHit
+            } else if closer < N::zero() {
+                CastResult::Hit(N::zero())
+            } else {
+                CastResult::Hit(closer)
+            };
+        }
+
+        let (tn, un, denom) = if denom < N::zero() {
+            (-qp.cross(s), -qp.cross(self.dir), -denom)
+        } else {
+            (qp.cross(s), qp.cross(self.dir), denom)
+        };
+
+        if tn >= N::zero() && Range::new(N::zero(), denom).contains(un) {
+            CastResult::Hit(tn / denom)
+        } else {
+            CastResult::NoHit
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_intersect() {
+        let a = segment(vec2(0, 0), vec2(10, 10));
+        let b = segment(vec2(0, 10), vec2(10, 0));
+        assert_eq!(a.intersect(&b), SegmentIntersection::Point(vec2(5, 5)));
+
+        let c = segment(vec2(5, 5), vec2(20, 20));
+        assert_eq!(
+            a.intersect(&c),
+            SegmentIntersection::Overlap(segment(vec2(5, 5), vec2(10, 10)))
+        );
+
+        let d = segment(vec2(10, 10), vec2(10, 20));
+        assert_eq!(a.intersect(&d), SegmentIntersection::Point(vec2(10, 10)));
+
+        let e = segment(vec2(1, 0), vec2(11, 10));
+        assert_eq!(a.intersect(&e), SegmentIntersection::NoIntersection);
+
+        let f = segment(vec2(11, 11), vec2(20, 20));
+        assert_eq!(a.intersect(&f), SegmentIntersection::NoIntersection);
+
+        //Segments with no length.
+        let p = segment(vec2(0, 0), vec2(0, 0));
+        let q = segment(vec2(5, 0), vec2(5, 0));
+        assert_eq!(p.intersect(&q), SegmentIntersection::NoIntersection);
+        assert_eq!(p.intersect(&p), SegmentIntersection::Point(vec2(0, 0)));
+        assert_eq!(q.intersect(&a), SegmentIntersection::NoIntersection);
+        let r = segment(vec2(5, 5), vec2(5, 5));
+        assert_eq!(r.intersect(&a), SegmentIntersection::Point(vec2(5, 5)));
+        assert_eq!(a.intersect(&r), SegmentIntersection::Point(vec2(5, 5)));
+        let h = segment(vec2(-10, 0), vec2(10, 0));
+        assert_eq!(q.intersect(&h), SegmentIntersection::Point(vec2(5, 0)));
+        assert_eq!(
+            segment(vec2(11, 0), vec2(11, 0)).intersect(&h),
+            SegmentIntersection::NoIntersection
+        );
+    }
+
+    #[test]
+    fn test_segment_rect() {
+        let r = rect(0.0, 10.0, 0.0, 10.0);
+
+        let s = segment(vec2(-5.0, 5.0), vec2(15.0, 5.0));
+        assert_eq!(
+            s.clip_to_rect(&r),
+            Some(segment(vec2(0.0, 5.0), vec2(10.0, 5.0)))
+        );
+
+        let s = segment(vec2(-5.0, -5.0), vec2(5.0, 5.0));
+        assert_eq!(
+            s.clip_to_rect(&r),
+            Some(segment(vec2(0.0, 0.0), vec2(5.0, 5.0)))
+        );
+
+        let s = segment(vec2(-5.0, 5.0), vec2(5.0, 15.0));
+        assert_eq!(
+            s.clip_to_rect(&r),
+            Some(segment(vec2(0.0, 10.0), vec2(0.0, 10.0)))
+        );
+
+        let s = segment(vec2(-5.0, 6.0), vec2(5.0, 16.0));
+        assert!(!s.intersects_rect(&r));
+
+        let s = segment(vec2(0, 0), vec2(10, 0));
+        assert_eq!(s.closest_point(vec2(3, 5)), vec2(3, 0));
+        assert_eq!(s.distance_squared_to_point(vec2(13, 4)), 25);
+    }
+
+    #[test]
+    fn test_cast_to_segment() {
+        let s = segment(vec2(0, 0), vec2(0, 10));
+
+        assert_eq!(
+            ray(vec2(-5, 5), vec2(1, 0)).cast_to_segment(&s),
+            CastResult::Hit(5)
+        );
+        assert_eq!(
+            ray(vec2(-5, 5), vec2(-1, 0)).cast_to_segment(&s),
+            CastResult::NoHit
+        );
+        assert_eq!(
+            ray(vec2(0, -5), vec2(0, 1)).cast_to_segment(&s),
+            CastResult::Hit(5)
+        );
+        assert_eq!(
+            ray(vec2(-5, 11), vec2(1, 0)).cast_to_segment(&s),
+            CastResult::NoHit
+        );
+    }
+}
@@ -0,0 +1,45 @@
+//! serde only implements its traits for arrays of a fixed set of lengths,
+//! so const generic arrays are (de)serialized through these functions via `#[serde(with)]`.
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub fn serialize<S: Serializer, T: Serialize, const D: usize>(
+    arr: &[T; D],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut tup = serializer.serialize_tuple(D)?;
+    for a in arr.iter() {
+        tup.serialize_element(a)?;
+    }
+    tup.end()
+}
+
+pub fn deserialize<'de, De: Deserializer<'de>, T: Deserialize<'de>, const D: usize>(
+    deserializer: De,
+) -> Result<[T; D], De::Error> {
+    struct ArrVisitor<T, const D: usize>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>, const D: usize> Visitor<'de> for ArrVisitor<T, D> {
+        type Value = [T; D];
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "an array of length {}", D)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<[T; D], A::Error> {
+            let mut arr: [Option<T>; D] = core::array::from_fn(|_| None);
+            for (i, a) in arr.iter_mut().enumerate() {
+                *a = Some(
+                    seq.next_element()?
+                        .ok_or_else(|| A::Error::invalid_length(i, &self))?,
+                );
+            }
+            Ok(arr.map(|a| a.unwrap()))
+        }
+    }
+
+    deserializer.deserialize_tuple(D, ArrVisitor(PhantomData))
+}
@@ -0,0 +1,102 @@
+use crate::*;
+
+#[inline(always)]
+fn cmp_start<A: Axis, N: PartialOrd + Copy>(
+    axis: A,
+    a: &Rect<N>,
+    b: &Rect<N>,
+) -> core::cmp::Ordering {
+    let a = a.get_range(axis).start;
+    let b = b.get_range(axis).start;
+    a.partial_cmp(&b).unwrap_or(core::cmp::Ordering::Equal)
+}
+
+///Finds every pair of intersecting items using sweep and prune.
+///
+///The items are sorted by the start of their range along the specified axis.
+///Then each item is only compared against the items after it that intersect it along that axis,
+///and the pair is reported if they also intersect along the other axis.
+///Sweeping along the axis the items are most spread out along prunes the most pairs.
+///This does not allocate.
+pub fn sweep_and_prune<A: Axis, T: HasRect>(
+    axis: A,
+    items: &mut [T],
+    mut func: impl FnMut(&mut T, &mut T),
+) where
+    T::Num: PartialOrd + Copy,
+{
+    items.sort_unstable_by(|a, b| cmp_start(axis, a.get_rect(), b.get_rect()));
+
+    for i in 0..items.len() {
+        let (first, rest) = items[i..].split_first_mut().unwrap();
+        for second in rest.iter_mut() {
+            let a = first.get_rect();
+            let b = second.get_rect();
+
+            //Every item after this one starts even later, so none of them can intersect either.
+            if !a.get_range(axis).intersects(b.get_range(axis)) {
+                break;
+            }
+            if a.get_range(axis.next())
+                .intersects(b.get_range(axis.next()))
+            {
+                func(first, second);
+            }
+        }
+    }
+}
+
+///Like `sweep_and_prune` but leaves the items in place and returns the indices of every intersecting pair.
+///The lower index of each pair comes first.
+#[cfg(feature = "alloc")]
+pub fn sweep_and_prune_pairs<A: Axis, T: HasRect>(
+    axis: A,
+    items: &[T],
+) -> alloc::vec::Vec<(usize, usize)>
+where
+    T::Num: PartialOrd + Copy,
+{
+    let mut indices: alloc::vec::Vec<_> = items
+        .iter()
+        .enumerate()
+        .map(|(i, a)| (*a.get_rect(), i))
+        .collect();
+    let mut pairs = alloc::vec::Vec::new();
+    sweep_and_prune(axis, &mut indices, |a, b| {
+        pairs.push(if a.1 < b.1 { (a.1, b.1) } else { (b.1, a.1) })
+    });
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_and_prune() {
+        let mut rects = [
+            rect(0, 10, 0, 10),
+            rect(20, 30, 0, 10),
+            rect(5, 25, 20, 30),
+            rect(8, 22, 5, 25),
+            rect(40, 50, 40, 50),
+        ];
+
+        let mut count = 0;
+        sweep_and_prune(YAXIS, &mut rects, |a, b| {
+            assert!(a.intersects_rect(b));
+            count += 1;
+        });
+        assert_eq!(count, 3);
+
+        //Touching counts as intersecting.
+        let mut rects = [
+            rect(0.0f32, 1.0, 0.0, 1.0),
+            rect(1.0, 2.0, 1.0, 2.0),
+            rect(2.5, 3.0, 0.0, 1.0),
+        ];
+        let mut count = 0;
+        sweep_and_prune(XAXIS, &mut rects, |_, _| count += 1);
+        assert_eq!(count, 1);
+    }
+}
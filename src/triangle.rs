@@ -0,0 +1,230 @@
+use crate::*;
+use core::cmp::Ordering;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+///Convenience function to create a Triangle.
+#[inline(always)]
+pub fn triangle<N>(a: Vec2<N>, b: Vec2<N>, c: Vec2<N>) -> Triangle<N> {
+    Triangle { a, b, c }
+}
+
+///A triangle with points in either winding order.
+///Points on the edges of the triangle are considered inside of it.
+#[derive(Default, Hash, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub struct Triangle<N> {
+    pub a: Vec2<N>,
+    pub b: Vec2<N>,
+    pub c: Vec2<N>,
+}
+
+impl<N: Copy> Triangle<N> {
+    #[inline(always)]
+    pub fn inner_as<B: 'static + Copy>(&self) -> Triangle<B>
+    where
+        N: num_traits::AsPrimitive<B>,
+    {
+        triangle(self.a.inner_as(), self.b.inner_as(), self.c.inner_as())
+    }
+
+    #[inline(always)]
+    pub fn points(&self) -> [Vec2<N>; 3] {
+        [self.a, self.b, self.c]
+    }
+
+    ///Returns the edges from a to b, b to c, and c to a.
+    #[inline(always)]
+    pub fn edges(&self) -> [Segment<N>; 3] {
+        [
+            segment(self.a, self.b),
+            segment(self.b, self.c),
+            segment(self.c, self.a),
+        ]
+    }
+}
+
+impl<N: PartialOrd + Copy> Triangle<N> {
+    ///Returns the smallest rect that contains all three points.
+    #[inline(always)]
+    pub fn bounding_rect(&self) -> Rect<N> {
+        let mut r = rect(self.a.x, self.a.x, self.a.y, self.a.y);
+        r.grow_to_fit_point(self.b).grow_to_fit_point(self.c);
+        r
+    }
+}
+
+impl<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy> Triangle<N> {
+    ///Twice the area of the triangle.
+    ///Positive if the points are counter clockwise when the y axis points up.
+    ///This is exact for integers.
+    #[inline(always)]
+    #[must_use]
+    pub fn signed_area2(&self) -> N {
+        (self.b - self.a).cross(self.c - self.a)
+    }
+
+    ///For integers, the area is truncated.
+    #[inline(always)]
+    #[must_use]
+    pub fn area(&self) -> N {
+        self.signed_area2().abs() / (N::one() + N::one())
+    }
+
+    ///Returns the weights of a, b and c that add up to one and whose weighted sum of the points is the specified point.
+    ///Returns `None` if the triangle has no area.
+    ///For integers, the weights are truncated, so use `contains_point` to check if a point is inside.
+    pub fn barycentric(&self, point: Vec2<N>) -> Option<Vec3<N>> {
+        let area2 = self.signed_area2();
+        if area2 == N::zero() {
+            return None;
+        }
+        let [u, v, w] = self.weights(point);
+        Some(vec3(u / area2, v / area2, w / area2))
+    }
+
+    ///Twice the signed areas of the triangles made by the point and each edge opposite a, b and c.
+    #[inline(always)]
+    fn weights(&self, point: Vec2<N>) -> [N; 3] {
+        [
+            (self.c - self.b).cross(point - self.b),
+            (self.a - self.c).cross(point - self.c),
+            (self.b - self.a).cross(point - self.a),
+        ]
+    }
+
+    ///Returns true if the triangle touches the rect.
+    ///This uses the separating axis theorem and is exact for integers.
+    #[inline(always)]
+    pub fn intersects_rect(&self, rect: &Rect<N>) -> bool {
+        crate::polygon::intersects(&self.points(), &rect.get_corners())
+    }
+}
+
+impl<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy + Predicates> Triangle<N> {
+    ///Returns `Greater` if the points are counter clockwise when the y axis points up,
+    ///`Less` if they are clockwise, and `Equal` if they are on one line.
+    ///This is exact, even for nearly flat triangles. See `orient2d`.
+    #[inline(always)]
+    pub fn orientation(&self) -> Ordering {
+        orient2d(self.a, self.b, self.c)
+    }
+
+    ///Returns true if the point is inside or on an edge of the triangle.
+    ///If the triangle has no area, returns true if the point is on one of its edges.
+    pub fn contains_point(&self, point: Vec2<N>) -> bool {
+        let sides = [
+            orient2d(self.b, self.c, point),
+            orient2d(self.c, self.a, point),
+            orient2d(self.a, self.b, point),
+        ];
+        if self.orientation() == Ordering::Equal {
+            return sides.iter().all(|&s| s == Ordering::Equal)
+                && self.bounding_rect().contains_point(point);
+        }
+
+        let left = sides.contains(&Ordering::Greater);
+        let right = sides.contains(&Ordering::Less);
+        !(left && right)
+    }
+
+    ///Returns the point in the triangle closest to the specified point.
+    pub fn closest_point(&self, point: Vec2<N>) -> Vec2<N> {
+        if self.contains_point(point) {
+            return point;
+        }
+
+        let [ab, bc, ca] = self.edges().map(|e| e.closest_point(point));
+        let dis = |p: Vec2<N>| p.distance_squared_to_point(point);
+        let closer = |x: Vec2<N>, y: Vec2<N>| if dis(y) < dis(x) { y } else { x };
+        closer(closer(ab, bc), ca)
+    }
+}
+
+impl<N: num_traits::Num + num_traits::Signed + PartialOrd + Copy + Predicates> Ray<N> {
+    ///Checks if a ray intersects a triangle.
+    ///If the ray starts inside the triangle, the tval is zero.
+    pub fn cast_to_triangle(&self, tri: &Triangle<N>) -> CastResult<N> {
+        if tri.contains_point(self.point) {
+            return CastResult::Hit(N::zero());
+        }
+        crate::polygon::cast_to_edges(self, &tri.points())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_triangle() {
+        let tri = triangle(vec2(0, 0), vec2(10, 0), vec2(0, 10));
+        assert_eq!(tri.signed_area2(), 100);
+        assert_eq!(tri.area(), 50);
+        assert_eq!(tri.orientation(), Ordering::Greater);
+        assert_eq!(triangle(tri.a, tri.c, tri.b).orientation(), Ordering::Less);
+        assert_eq!(tri.bounding_rect(), rect(0, 10, 0, 10));
+
+        assert!(tri.contains_point(vec2(5, 5)));
+        assert!(!tri.contains_point(vec2(6, 5)));
+
+        assert_eq!(tri.closest_point(vec2(2, 3)), vec2(2, 3));
+        assert_eq!(tri.closest_point(vec2(-5, 4)), vec2(0, 4));
+        assert_eq!(tri.closest_point(vec2(10, 10)), vec2(5, 5));
+        assert_eq!(tri.closest_point(vec2(20, -5)), vec2(10, 0));
+
+        assert!(tri.intersects_rect(&rect(5, 20, 5, 20)));
+        //Overlaps the bounding rect, but not the triangle.
+        assert!(!tri.intersects_rect(&rect(6, 20, 6, 20)));
+
+        assert_eq!(
+            ray(vec2(20, 2), vec2(-1, 0)).cast_to_triangle(&tri),
+            CastResult::Hit(12)
+        );
+        assert_eq!(
+            ray(vec2(1, 1), vec2(1, 0)).cast_to_triangle(&tri),
+            CastResult::Hit(0)
+        );
+        assert_eq!(
+            ray(vec2(20, 11), vec2(-1, 0)).cast_to_triangle(&tri),
+            CastResult::NoHit
+        );
+
+        //A triangle with no area only contains the points between its ends.
+        let flat = triangle(vec2(0, 0), vec2(5, 0), vec2(10, 0));
+        assert!(flat.contains_point(vec2(7, 0)));
+        assert!(!flat.contains_point(vec2(11, 0)));
+        assert_eq!(flat.barycentric(vec2(7, 0)), None);
+    }
+
+    #[test]
+    fn test_triangle_exact() {
+        //The first point is moved off of the line y=x by a few ulps,
+        //which is too small for a plain cross product to see.
+        let ulp = f64::EPSILON / 2.0;
+        for i in 0..16 {
+            for j in 0..16 {
+                let a = vec2(0.5 + i as f64 * ulp, 0.5 + j as f64 * ulp);
+                let tri = triangle(a, vec2(12.0, 12.0), vec2(24.0, 24.0));
+                assert_eq!(tri.orientation(), j.cmp(&i));
+            }
+        }
+
+        let tri = triangle(
+            vec2(0.5, 0.5 + 9.0 * ulp),
+            vec2(12.0, 12.0),
+            vec2(24.0, 24.0),
+        );
+        assert!(!tri.contains_point(vec2(6.0, 6.0)));
+        assert!(tri.contains_point(vec2(18.0, 18.0)));
+    }
+
+    #[test]
+    fn test_barycentric() {
+        let tri = triangle(vec2(0.0, 0.0), vec2(4.0, 0.0), vec2(0.0, 8.0));
+        assert_eq!(tri.barycentric(vec2(0.0, 0.0)), Some(vec3(1.0, 0.0, 0.0)));
+        assert_eq!(tri.barycentric(vec2(1.0, 2.0)), Some(vec3(0.5, 0.25, 0.25)));
+        assert_eq!(tri.barycentric(vec2(-4.0, 0.0)), Some(vec3(2.0, -1.0, 0.0)));
+    }
+}
@@ -135,6 +135,16 @@ impl<S: Mul<Output = S> + Div<Output = S> + Add<Output = S> + Copy> Vec2<S> {
         self.x * other.x + self.y * other.y
     }
 }
+
+impl<S: Mul<Output = S> + Sub<Output = S> + Copy> Vec2<S> {
+    ///The z component of the 3D cross product of the two vectors.
+    ///Positive if other is counter clockwise of self, assuming y points up.
+    #[inline(always)]
+    #[must_use]
+    pub fn cross(&self, other: Vec2<S>) -> S {
+        self.x * other.y - self.y * other.x
+    }
+}
 impl<S: FloatCore> Vec2<S> {
     #[inline(always)]
     pub fn is_nan(&self) -> bool {
@@ -142,7 +152,7 @@ impl<S: FloatCore> Vec2<S> {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "libm"))]
 impl<S: num_traits::Float> Vec2<S> {
     #[inline(always)]
     pub fn truncate_at(&self, mag: S) -> Vec2<S> {
@@ -328,6 +338,7 @@ impl<S: Neg<Output = S>> Neg for Vec2<S> {
     }
 }
 
+#[allow(unused_attributes)]
 impl<S: Zero + Eq + Copy> Zero for Vec2<S> {
     #[inline(always)]
     fn zero() -> Vec2<S> {
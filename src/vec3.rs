@@ -0,0 +1,193 @@
+use crate::{Axis3, AxisDyn3};
+use core::convert::TryInto;
+use core::ops::*;
+use num_traits::Zero;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+///Convenience function to create a 3D vector.
+#[inline(always)]
+pub const fn vec3<N>(x: N, y: N, z: N) -> Vec3<N> {
+    Vec3 { x, y, z }
+}
+
+///Convenience function to create a 3D vector where all components are the same.
+#[inline(always)]
+pub fn vec3same<N: Copy>(a: N) -> Vec3<N> {
+    Vec3 { x: a, y: a, z: a }
+}
+
+///A 3D vector.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[must_use]
+pub struct Vec3<N> {
+    pub x: N,
+    pub y: N,
+    pub z: N,
+}
+
+impl<B> Vec3<B> {
+    ///Get the value of one axis.
+    #[inline(always)]
+    #[must_use]
+    pub fn get_axis(&self, axis: impl Axis3) -> &B {
+        match axis.to_dyn3() {
+            AxisDyn3::X => &self.x,
+            AxisDyn3::Y => &self.y,
+            AxisDyn3::Z => &self.z,
+        }
+    }
+
+    ///Get the mutable value of one axis.
+    #[inline(always)]
+    #[must_use]
+    pub fn get_axis_mut(&mut self, axis: impl Axis3) -> &mut B {
+        match axis.to_dyn3() {
+            AxisDyn3::X => &mut self.x,
+            AxisDyn3::Y => &mut self.y,
+            AxisDyn3::Z => &mut self.z,
+        }
+    }
+
+    #[inline(always)]
+    pub fn inner_into<A>(self) -> Vec3<A>
+    where
+        B: Into<A>,
+    {
+        vec3(self.x.into(), self.y.into(), self.z.into())
+    }
+
+    #[inline(always)]
+    pub fn inner_try_into<A>(self) -> Result<Vec3<A>, B::Error>
+    where
+        B: TryInto<A>,
+    {
+        Ok(vec3(
+            self.x.try_into()?,
+            self.y.try_into()?,
+            self.z.try_into()?,
+        ))
+    }
+}
+
+impl<T> Vec3<T> {
+    #[inline(always)]
+    pub fn inner_as<B: 'static + Copy>(self) -> Vec3<B>
+    where
+        T: num_traits::AsPrimitive<B>,
+    {
+        vec3(self.x.as_(), self.y.as_(), self.z.as_())
+    }
+}
+
+impl<S: Mul<Output = S> + Add<Output = S> + Copy> Vec3<S> {
+    #[inline(always)]
+    #[must_use]
+    pub fn magnitude2(&self) -> S {
+        self.x * self.x + self.y * self.y + self.z * self.z
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn dot(&self, other: Vec3<S>) -> S {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl<S: Mul<Output = S> + Sub<Output = S> + Copy> Vec3<S> {
+    #[inline(always)]
+    pub fn cross(&self, other: Vec3<S>) -> Vec3<S> {
+        vec3(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+impl<B> From<[B; 3]> for Vec3<B> {
+    #[inline(always)]
+    fn from(a: [B; 3]) -> Self {
+        let [x, y, z] = a;
+        vec3(x, y, z)
+    }
+}
+
+impl<B> From<Vec3<B>> for [B; 3] {
+    #[inline(always)]
+    fn from(a: Vec3<B>) -> Self {
+        [a.x, a.y, a.z]
+    }
+}
+
+impl<S: Add<Output = S> + Copy> Add<Self> for Vec3<S> {
+    type Output = Self;
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        vec3(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<S: Sub<Output = S> + Copy> Sub<Self> for Vec3<S> {
+    type Output = Self;
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        vec3(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<S: Mul<Output = S> + Copy> Mul<S> for Vec3<S> {
+    type Output = Self;
+    #[inline(always)]
+    fn mul(self, rhs: S) -> Self {
+        vec3(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<S: Div<Output = S> + Copy> Div<S> for Vec3<S> {
+    type Output = Self;
+    #[inline(always)]
+    fn div(self, rhs: S) -> Self {
+        vec3(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
+impl<S: AddAssign<S> + Copy> AddAssign<Self> for Vec3<S> {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<S: SubAssign<S> + Copy> SubAssign<Self> for Vec3<S> {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl<S: Neg<Output = S>> Neg for Vec3<S> {
+    type Output = Vec3<S>;
+
+    #[inline]
+    fn neg(self) -> Vec3<S> {
+        vec3(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<S: Zero + Eq + Copy> Zero for Vec3<S> {
+    #[inline(always)]
+    fn zero() -> Vec3<S> {
+        vec3(S::zero(), S::zero(), S::zero())
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        *self == Vec3::zero()
+    }
+}
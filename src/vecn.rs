@@ -0,0 +1,206 @@
+use crate::vec2::vec2;
+use crate::vec3::vec3;
+use crate::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use core::ops::*;
+
+///Convenience function to create a VecN.
+#[inline(always)]
+pub const fn vecn<N, const D: usize>(coords: [N; D]) -> VecN<N, D> {
+    VecN { coords }
+}
+
+///A D dimensional vector.
+///Axis are referred to by their index, so x is 0, y is 1, z is 2, and so on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "N: Serialize", deserialize = "N: Deserialize<'de>"))
+)]
+#[must_use]
+pub struct VecN<N, const D: usize> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
+    pub coords: [N; D],
+}
+
+impl<N: Default, const D: usize> Default for VecN<N, D> {
+    #[inline(always)]
+    fn default() -> Self {
+        vecn(core::array::from_fn(|_| N::default()))
+    }
+}
+
+impl<N, const D: usize> VecN<N, D> {
+    ///Get the value of one axis.
+    #[inline(always)]
+    #[must_use]
+    pub fn get_axis(&self, axis: usize) -> &N {
+        &self.coords[axis]
+    }
+
+    ///Get the mutable value of one axis.
+    #[inline(always)]
+    #[must_use]
+    pub fn get_axis_mut(&mut self, axis: usize) -> &mut N {
+        &mut self.coords[axis]
+    }
+
+    #[inline(always)]
+    pub fn inner_into<A>(self) -> VecN<A, D>
+    where
+        N: Into<A>,
+    {
+        vecn(self.coords.map(|a| a.into()))
+    }
+}
+
+impl<N: Copy, const D: usize> VecN<N, D> {
+    #[inline(always)]
+    pub fn inner_as<B: 'static + Copy>(&self) -> VecN<B, D>
+    where
+        N: num_traits::AsPrimitive<B>,
+    {
+        vecn(self.coords.map(|a| a.as_()))
+    }
+
+    ///Build a new vector by combining each component of two vectors.
+    #[inline(always)]
+    pub fn zip_with<M: Copy, O>(
+        &self,
+        other: &VecN<M, D>,
+        mut func: impl FnMut(N, M) -> O,
+    ) -> VecN<O, D> {
+        vecn(core::array::from_fn(|i| {
+            func(self.coords[i], other.coords[i])
+        }))
+    }
+}
+
+impl<S: num_traits::Zero + Mul<Output = S> + Copy, const D: usize> VecN<S, D> {
+    #[inline(always)]
+    #[must_use]
+    pub fn dot(&self, other: &VecN<S, D>) -> S {
+        self.coords
+            .iter()
+            .zip(other.coords.iter())
+            .fold(S::zero(), |acc, (&a, &b)| acc + a * b)
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn magnitude2(&self) -> S {
+        self.dot(self)
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn distance_squared_to_point(&self, point: &VecN<S, D>) -> S
+    where
+        S: Sub<Output = S>,
+    {
+        let d = *point - *self;
+        d.magnitude2()
+    }
+}
+
+impl<N, const D: usize> Index<usize> for VecN<N, D> {
+    type Output = N;
+    #[inline(always)]
+    fn index(&self, axis: usize) -> &N {
+        &self.coords[axis]
+    }
+}
+
+impl<N, const D: usize> IndexMut<usize> for VecN<N, D> {
+    #[inline(always)]
+    fn index_mut(&mut self, axis: usize) -> &mut N {
+        &mut self.coords[axis]
+    }
+}
+
+impl<B, const D: usize> From<[B; D]> for VecN<B, D> {
+    #[inline(always)]
+    fn from(a: [B; D]) -> Self {
+        vecn(a)
+    }
+}
+
+impl<B, const D: usize> From<VecN<B, D>> for [B; D] {
+    #[inline(always)]
+    fn from(a: VecN<B, D>) -> Self {
+        a.coords
+    }
+}
+
+impl<B> From<Vec2<B>> for VecN<B, 2> {
+    #[inline(always)]
+    fn from(a: Vec2<B>) -> Self {
+        vecn([a.x, a.y])
+    }
+}
+
+impl<B> From<VecN<B, 2>> for Vec2<B> {
+    #[inline(always)]
+    fn from(a: VecN<B, 2>) -> Self {
+        let [x, y] = a.coords;
+        vec2(x, y)
+    }
+}
+
+impl<B> From<Vec3<B>> for VecN<B, 3> {
+    #[inline(always)]
+    fn from(a: Vec3<B>) -> Self {
+        vecn([a.x, a.y, a.z])
+    }
+}
+
+impl<B> From<VecN<B, 3>> for Vec3<B> {
+    #[inline(always)]
+    fn from(a: VecN<B, 3>) -> Self {
+        let [x, y, z] = a.coords;
+        vec3(x, y, z)
+    }
+}
+
+impl<S: Add<Output = S> + Copy, const D: usize> Add<Self> for VecN<S, D> {
+    type Output = Self;
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        self.zip_with(&rhs, |a, b| a + b)
+    }
+}
+
+impl<S: Sub<Output = S> + Copy, const D: usize> Sub<Self> for VecN<S, D> {
+    type Output = Self;
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        self.zip_with(&rhs, |a, b| a - b)
+    }
+}
+
+impl<S: Mul<Output = S> + Copy, const D: usize> Mul<S> for VecN<S, D> {
+    type Output = Self;
+    #[inline(always)]
+    fn mul(self, rhs: S) -> Self {
+        vecn(self.coords.map(|a| a * rhs))
+    }
+}
+
+impl<S: Div<Output = S> + Copy, const D: usize> Div<S> for VecN<S, D> {
+    type Output = Self;
+    #[inline(always)]
+    fn div(self, rhs: S) -> Self {
+        vecn(self.coords.map(|a| a / rhs))
+    }
+}
+
+impl<S: Neg<Output = S>, const D: usize> Neg for VecN<S, D> {
+    type Output = VecN<S, D>;
+
+    #[inline]
+    fn neg(self) -> VecN<S, D> {
+        vecn(self.coords.map(|a| -a))
+    }
+}
@@ -52,3 +52,475 @@ fn test() {
         }
     }
 }
+
+#[test]
+fn test_axis3() {
+    use axgeom::Axis3;
+
+    let a = axgeom::XAXIS;
+    let b = a.next3();
+    let c = b.next3();
+    let d = c.next3();
+    assert!(d.is_equal_to3(a));
+
+    let bb = box3(0, 10, 0, 20, 0, 30);
+
+    //Recurse through each axis once, splitting in the middle each time.
+    fn generic<A: Axis3>(a: A, bb: Box3<i32>, depth: usize) -> Box3<i32> {
+        if depth == 0 {
+            return bb;
+        }
+        let r = bb.get_range(a);
+        let (l, _) = bb.subdivide(a, r.start + r.distance() / 2);
+        generic(a.next3(), l, depth - 1)
+    }
+
+    assert_eq!(generic(a, bb, 3), box3(0, 5, 0, 10, 0, 15));
+    assert_eq!(*vec3(1, 2, 3).get_axis(c), 3);
+    assert!(bb.contains_point(vec3(10, 20, 30)));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_roundtrip() {
+    use serde::{de::DeserializeOwned, Serialize};
+
+    fn roundtrip<T: Serialize + DeserializeOwned + PartialEq + core::fmt::Debug>(a: T) {
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(serde_json::from_str::<T>(&json).unwrap(), a);
+
+        let bin = bincode::serialize(&a).unwrap();
+        assert_eq!(bincode::deserialize::<T>(&bin).unwrap(), a);
+    }
+
+    roundtrip(range(-1.5f32, 2.0));
+    roundtrip(rect(0, 10, -5, 5));
+    roundtrip(CastResult::Hit(3.0f64));
+    roundtrip(CastResult::<f64>::NoHit);
+    roundtrip(AxisDyn::Y);
+    roundtrip(AxisDyn3::Z);
+    roundtrip(box3(0u8, 1, 2, 3, 4, 5));
+    roundtrip(aabb([
+        range(0i64, 1),
+        range(2, 3),
+        range(4, 5),
+        range(6, 7),
+    ]));
+    roundtrip(vecn([1u16, 2, 3, 4, 5]));
+
+    //Ray does not implement PartialEq.
+    let r = ray(vec2(1.0f32, 2.0), vec2(0.0, -1.0));
+    let json = serde_json::to_string(&r).unwrap();
+    let r2: Ray<f32> = serde_json::from_str(&json).unwrap();
+    assert_eq!((r.point, r.dir), (r2.point, r2.dir));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_rect_region() {
+    let region: RectRegion<i32> = vec![rect(0, 10, 0, 10), rect(5, 15, 5, 15)]
+        .into_iter()
+        .collect();
+    let json = serde_json::to_string(&region).unwrap();
+    assert_eq!(
+        serde_json::from_str::<RectRegion<i32>>(&json).unwrap(),
+        region
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_polygon() {
+    let corners = rect(0, 10, 0, 10).get_corners();
+    let poly: ConvexPolygon<i32, 8> = ConvexPolygon::from_points(corners).unwrap();
+    let json = serde_json::to_string(&poly).unwrap();
+    assert_eq!(
+        serde_json::from_str::<ConvexPolygon<i32, 8>>(&json).unwrap(),
+        poly
+    );
+
+    let poly = ConvexPolygonVec::new(corners.to_vec());
+    let json = serde_json::to_string(&poly).unwrap();
+    assert_eq!(
+        serde_json::from_str::<ConvexPolygonVec<i32>>(&json).unwrap(),
+        poly
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_range_set() {
+    let set: RangeSet<i32> = vec![range(0, 5), range(10, 20)].into_iter().collect();
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(serde_json::from_str::<RangeSet<i32>>(&json).unwrap(), set);
+
+    let set: ArrayRangeSet<i32, 4> = ArrayRangeSet::from_range(range(0, 5)).unwrap();
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(
+        serde_json::from_str::<ArrayRangeSet<i32, 4>>(&json).unwrap(),
+        set
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_compact() {
+    #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+    struct Level {
+        #[serde(with = "axgeom::compact::rect")]
+        bounds: Rect<i32>,
+        #[serde(with = "axgeom::compact::vec2")]
+        spawn: Vec2<i32>,
+    }
+
+    let level = Level {
+        bounds: rect(0, 10, 20, 30),
+        spawn: vec2(5, 25),
+    };
+    let json = serde_json::to_string(&level).unwrap();
+    assert_eq!(json, r#"{"bounds":[0,10,20,30],"spawn":[5,25]}"#);
+    assert_eq!(serde_json::from_str::<Level>(&json).unwrap(), level);
+
+    let json = serde_json::to_string(&rect(0, 10, 20, 30)).unwrap();
+    assert_eq!(
+        json,
+        r#"{"x":{"start":0,"end":10},"y":{"start":20,"end":30}}"#
+    );
+}
+
+#[test]
+fn test_cast_full() {
+    let aabb = rect(0, 10, 0, 10);
+
+    let r = ray(vec2(-6isize, 5), vec2(1, 0));
+    let hit = r.cast_to_rect_full(&aabb).unwrap();
+    assert_eq!(hit.t_enter, 6);
+    assert_eq!(hit.t_exit, 16);
+    assert_eq!(hit.point, vec2(0, 5));
+    assert_eq!(hit.normal, vec2(-1, 0));
+    assert_eq!(hit.axis, AxisDyn::X);
+
+    let r = ray(vec2(5isize, 15), vec2(0, -1));
+    let hit = r.cast_to_rect_full(&aabb).unwrap();
+    assert_eq!((hit.t_enter, hit.t_exit), (5, 15));
+    assert_eq!(hit.normal, vec2(0, 1));
+    assert_eq!(hit.axis, AxisDyn::Y);
+
+    let r = ray(vec2(5isize, 5), vec2(0, 1));
+    let hit = r.cast_to_rect_full(&aabb).unwrap();
+    assert_eq!((hit.t_enter, hit.t_exit), (0, 5));
+    assert_eq!(hit.normal, vec2(0, 0));
+
+    let r = ray(vec2(-6isize, 5), vec2(-1, 0));
+    assert_eq!(r.cast_to_rect_full(&aabb), None);
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn test_cast_circle_full() {
+    let r = ray(vec2(-5.0f64, 0.0), vec2(1.0, 0.0));
+    let hit = r.cast_to_circle_full(&circle(vec2(0.0, 0.0), 2.0)).unwrap();
+    assert_eq!((hit.t_enter, hit.t_exit), (3.0, 7.0));
+    assert_eq!(hit.point, vec2(-2.0, 0.0));
+    assert_eq!(hit.normal, vec2(-1.0, 0.0));
+    assert_eq!(hit.axis, AxisDyn::X);
+    assert_eq!(
+        r.cast_to_circle(&circle(vec2(0.0, 0.0), 2.0)),
+        CastResult::Hit(hit.t_enter)
+    );
+
+    let r = ray(vec2(-5.0f64, 3.0), vec2(1.0, 0.0));
+    assert_eq!(r.cast_to_circle_full(&circle(vec2(0.0, 0.0), 2.0)), None);
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn test_cast_circle() {
+    let center = vec2(0.0f32, 0.0);
+
+    //Starting inside the circle.
+    let r = ray(vec2(0.5f32, 0.0), vec2(0.0, 1.0));
+    assert_eq!(r.cast_to_circle(&circle(center, 1.0)), CastResult::Hit(0.0));
+
+    //Moving away from the circle.
+    let r = ray(vec2(0.0f32, 5.0), vec2(0.0, 1.0));
+    assert_eq!(r.cast_to_circle(&circle(center, 1.0)), CastResult::NoHit);
+
+    //Tangent to the circle.
+    let r = ray(vec2(-5.0f32, 1.0), vec2(1.0, 0.0));
+    assert_eq!(r.cast_to_circle(&circle(center, 1.0)), CastResult::Hit(5.0));
+
+    //Unnormalized direction.
+    let r = ray(vec2(0.0f64, -10.0), vec2(0.0, 4.0));
+    assert_eq!(
+        r.cast_to_circle(&circle(vec2(0.0, 0.0), 2.0)),
+        CastResult::Hit(2.0)
+    );
+
+    assert_eq!(vec2(3.0f32, 4.0).magnitude(), 5.0);
+    assert_eq!(vec2(3.0f32, 4.0).truncate_at(1.0), vec2(0.6, 0.8));
+}
+
+#[test]
+fn test_sweep() {
+    let wall = rect(10.0f32, 20.0, 0.0, 10.0);
+
+    //Moving right into the wall.
+    let player = rect(0.0, 4.0, 2.0, 6.0);
+    let hit = player.sweep_to_rect(vec2(12.0, 0.0), &wall).unwrap();
+    assert_eq!(hit.normal, vec2(-1.0, 0.0));
+    assert_eq!(hit.point, vec2(6.0, 2.0));
+
+    //Not far enough to reach the wall.
+    assert_eq!(player.sweep_to_rect(vec2(5.0, 0.0), &wall), None);
+
+    //Falling onto the top of the wall.
+    let player = rect(12.0, 16.0, -10.0, -6.0);
+    let hit = player.sweep_to_rect(vec2(0.0, 12.0), &wall).unwrap();
+    assert_eq!(hit.t_enter, 0.5);
+    assert_eq!(hit.normal, vec2(0.0, -1.0));
+    assert_eq!(hit.axis, AxisDyn::Y);
+
+    //The same thing, but casting the center of the player.
+    let hit2 = ray(vec2(14.0, -8.0), vec2(0.0, 12.0))
+        .cast_box_to_rect(vec2(2.0, 2.0), &wall)
+        .unwrap();
+    assert_eq!((hit2.t_enter, hit2.normal), (hit.t_enter, hit.normal));
+}
+
+#[test]
+fn test_penetration() {
+    let a = rect(0, 10, 0, 10);
+
+    let (v, axis) = a.penetration(&rect(8, 20, -5, 15)).unwrap();
+    assert_eq!((v, axis), (vec2(-2, 0), AxisDyn::X));
+
+    let (v, axis) = a.penetration(&rect(-5, 15, -3, 1)).unwrap();
+    assert_eq!((v, axis), (vec2(0, 1), AxisDyn::Y));
+
+    assert_eq!(a.penetration(&rect(11, 20, 0, 10)), None);
+
+    //Standing in the corner of a floor and a wall.
+    let mut player = rect(8, 12, 6, 12);
+    let floor = rect(-100, 100, 10, 20);
+    let wall = rect(10, 20, -100, 100);
+    let offset = player.resolve_penetrations(&[floor, wall]);
+    assert_eq!(offset, vec2(-2, -2));
+    assert_eq!(player, rect(6, 10, 4, 10));
+}
+
+#[test]
+fn test_bounding_rect_from_iter() {
+    let points = [vec2(3, 4), vec2(-1, 2), vec2(5, -6)];
+    assert_eq!(Rect::from_points(points), Some(rect(-1, 5, -6, 4)));
+
+    let r: Option<Rect<i32>> = points.iter().copied().collect();
+    assert_eq!(r, Some(rect(-1, 5, -6, 4)));
+
+    let r: Option<Rect<i32>> = core::iter::empty::<Vec2<i32>>().collect();
+    assert_eq!(r, None);
+
+    let rects = [rect(0, 1, 0, 1), rect(5, 6, -3, 0)];
+    assert_eq!(Rect::union_all(rects), Some(rect(0, 6, -3, 1)));
+
+    let mut r = rect(0, 1, 0, 1);
+    r.extend(points);
+    r.extend(rects);
+    assert_eq!(r, rect(-1, 6, -6, 4));
+}
+
+#[test]
+fn test_rect_bounds() {
+    let closed = rect(0, 10, 0, 10);
+    let half: Rect<_, HalfOpen> = closed.into_bound();
+
+    //With closed ranges, points on the divider are in both halves.
+    let (l, r) = closed.subdivide(XAXIS, 5);
+    assert!(l.contains_point(vec2(5, 5)) && r.contains_point(vec2(5, 5)));
+
+    //With half open ranges, every point is in exactly one half.
+    let (l, r) = half.subdivide(XAXIS, 5);
+    assert!(!l.contains_point(vec2(5, 5)) && r.contains_point(vec2(5, 5)));
+
+    //Intersection agrees with intersects_rect for touching rects.
+    let touching = rect(10, 20, 0, 10);
+    assert!(closed.intersects_rect(&touching));
+    assert_eq!(
+        closed.get_intersect_rect(&touching),
+        Some(rect(10, 10, 0, 10))
+    );
+
+    let touching: Rect<_, HalfOpen> = touching.into_bound();
+    assert!(!half.intersects_rect(&touching));
+    assert_eq!(half.get_intersect_rect(&touching), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_range_set() {
+    let a: RangeSet<i32> = vec![range(10, 15), range(0, 5), range(4, 7)]
+        .into_iter()
+        .collect();
+    assert_eq!(a.ranges(), &[range(0, 7), range(10, 15)]);
+    assert_eq!(a.total_length(), 12);
+    assert!(a.contains(12));
+    assert!(!a.contains(8));
+
+    let b = RangeSet::from_range(range(3, 12));
+    assert_eq!(a.union(&b).ranges(), &[range(0, 15)]);
+    assert_eq!(a.intersection(&b).ranges(), &[range(3, 7), range(10, 12)]);
+    assert_eq!(a.difference(&b).ranges(), &[range(0, 3), range(12, 15)]);
+    assert_eq!(
+        a.symmetric_difference(&b).ranges(),
+        &[range(0, 3), range(7, 10), range(12, 15)]
+    );
+    assert_eq!(
+        a.complement(range(-5, 20)).ranges(),
+        &[range(-5, 0), range(7, 10), range(15, 20)]
+    );
+
+    //Touching closed ranges are merged.
+    let mut c = RangeSet::new();
+    c.insert(range(0.0, 1.0));
+    c.insert(range(2.0, 3.0));
+    c.insert(range(1.0, 2.0));
+    assert_eq!(c.ranges(), &[range(0.0, 3.0)]);
+}
+
+#[test]
+fn test_rect_difference() {
+    let a = rect(0, 10, 0, 10);
+
+    let pieces: Vec<_> = a.difference(&rect(3, 5, 4, 6)).collect();
+    assert_eq!(
+        pieces,
+        vec![
+            rect(0, 3, 0, 10),
+            rect(5, 10, 0, 10),
+            rect(3, 5, 0, 4),
+            rect(3, 5, 6, 10)
+        ]
+    );
+
+    let pieces: Vec<_> = a.difference(&rect(5, 20, -5, 20)).collect();
+    assert_eq!(pieces, vec![rect(0, 5, 0, 10)]);
+
+    //Touching rects don't cut anything.
+    let pieces: Vec<_> = a.difference(&rect(10, 20, 0, 10)).collect();
+    assert_eq!(pieces, vec![a]);
+
+    assert_eq!(a.difference(&rect(-1, 11, -1, 11)).count(), 0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_rect_region() {
+    let mut region = RectRegion::from_rect(rect(0, 10, 0, 10));
+    region.insert(rect(5, 15, 5, 15));
+    assert_eq!(region.area(), 175);
+    assert!(region.contains_point(vec2(12, 12)));
+    assert!(!region.contains_point(vec2(12, 2)));
+
+    region.remove(&rect(0, 15, 0, 5));
+    assert_eq!(region.area(), 125);
+    assert!(!region.contains_point(vec2(2, 2)));
+
+    let other: RectRegion<_> = vec![rect(0, 3, 0, 20), rect(2, 4, 0, 20)]
+        .into_iter()
+        .collect();
+    assert_eq!(other.area(), 80);
+    assert_eq!(region.intersection(&other).area(), 20);
+    assert_eq!(region.subtract(&other).area(), 105);
+    assert_eq!(region.union(&other).area(), 185);
+
+    //Degenerate rects have no area and are ignored.
+    let len = region.rects().len();
+    region.insert(rect(20, 20, 0, 10));
+    assert_eq!(region.rects().len(), len);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_sweep_and_prune_pairs() {
+    let rects: Vec<_> = (0..50)
+        .map(|i| {
+            let x = (i * 37 % 100) as f32;
+            let y = (i * 61 % 100) as f32;
+            rect(x, x + 12.0, y, y + 12.0)
+        })
+        .collect();
+
+    let mut expected = Vec::new();
+    for i in 0..rects.len() {
+        for j in i + 1..rects.len() {
+            if rects[i].intersects_rect(&rects[j]) {
+                expected.push((i, j));
+            }
+        }
+    }
+
+    for mut pairs in [
+        sweep_and_prune_pairs(XAXIS, &rects),
+        sweep_and_prune_pairs(YAXIS, &rects),
+    ] {
+        pairs.sort_unstable();
+        assert_eq!(pairs, expected);
+    }
+    assert!(!expected.is_empty());
+}
+
+#[test]
+fn test_grid_traverse() {
+    let bounds = rect(0.0, 40.0, 0.0, 30.0);
+    let cell_size = vec2(10.0, 10.0);
+
+    let cells: Vec<_> = ray(vec2(-5.0, 15.0), vec2(1.0, 0.0))
+        .grid_traverse(cell_size, &bounds, 100.0)
+        .collect();
+    assert_eq!(
+        cells,
+        vec![
+            (vec2(0, 1), 5.0, AxisDyn::X),
+            (vec2(1, 1), 15.0, AxisDyn::X),
+            (vec2(2, 1), 25.0, AxisDyn::X),
+            (vec2(3, 1), 35.0, AxisDyn::X)
+        ]
+    );
+
+    //Stops at the max tval.
+    assert_eq!(
+        ray(vec2(-5.0, 15.0), vec2(1.0, 0.0))
+            .grid_traverse(cell_size, &bounds, 20.0)
+            .count(),
+        2
+    );
+
+    //A diagonal ray starting inside the bounds, going up and to the left.
+    let cells: Vec<_> = ray(vec2(25.0, 12.0), vec2(-1.0, -0.5))
+        .grid_traverse(cell_size, &bounds, 100.0)
+        .map(|a| (a.0, a.2))
+        .collect();
+    assert_eq!(
+        cells,
+        vec![
+            (vec2(2, 1), AxisDyn::Y),
+            (vec2(2, 0), AxisDyn::Y),
+            (vec2(1, 0), AxisDyn::X),
+            (vec2(0, 0), AxisDyn::X)
+        ]
+    );
+
+    assert_eq!(
+        ray(vec2(-5.0, 35.0), vec2(1.0, 0.0))
+            .grid_traverse(cell_size, &bounds, 100.0)
+            .count(),
+        0
+    );
+}
+
+#[test]
+#[should_panic(expected = "the cell size of a grid must be positive")]
+fn test_grid_traverse_zero_cell_size() {
+    let bounds = rect(0.0, 40.0, 0.0, 20.0);
+    let _ = ray(vec2(-5.0, 5.0), vec2(1.0, 0.0)).grid_traverse(vec2(0.0, 10.0), &bounds, 100.0);
+}